@@ -14,5 +14,17 @@ fn main() -> Result<(), Box<dyn Error>> {
         println!("{:?}", end);
     }
 
+    // Same product through the transposed-rhs path, which multiplies contiguous rows.
+    let b_t = b.transpose(0, 1)?;
+
+    for _ in 0..10 {
+        let now = std::time::Instant::now();
+
+        let _c = &a.matmul_nt(&b_t)?;
+
+        let end = now.elapsed();
+        println!("nt: {:?}", end);
+    }
+
     Ok(())
 }
@@ -8,17 +8,33 @@ use std::{
     fmt::{Debug, Display, Formatter, Result},
 };
 
+/// Shows the layout of the tensor rather than its elements, for debugging views.
 impl<T: Debug + Copy> Debug for Tensor<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         f.debug_struct("Tensor")
-            .field("dtype", &type_name::<T>())
-            .field("dims", &self.ndims())
-            .field("elems", &self.numel())
-            .field("shape", &self.sizes())
+            .field("sizes", &self.sizes())
+            .field("strides", &self.strides())
+            .field("offset", &self.offset())
+            .field("numel", &self.numel())
+            .field("data_len", &self.data.len())
             .finish()
     }
 }
 
+fn summary<T: Copy>(tensor: &Tensor<T>, f: &mut Formatter<'_>) -> Result {
+    f.debug_struct("Tensor")
+        .field("dtype", &type_name::<T>())
+        .field("dims", &tensor.ndims())
+        .field("elems", &tensor.numel())
+        .field("shape", &tensor.sizes())
+        .finish()
+}
+
+/// Tensors with more elements than this are summarized, showing only `EDGE_ITEMS` elements at
+/// each end of every dimension with `...` in between.
+const SUMMARY_THRESHOLD: usize = 1000;
+const EDGE_ITEMS: usize = 3;
+
 impl<T: Display + Debug + Copy> Display for Tensor<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         let n = self.shape.ndims();
@@ -26,18 +42,20 @@ impl<T: Display + Debug + Copy> Display for Tensor<T> {
         if (1..=8).contains(&n) {
             let style = &format::consts::FORMAT_BOX_CHARS;
             let precision = 2;
+            let summarize = self.numel() > SUMMARY_THRESHOLD;
 
             let table = if n % 2 == 1 {
-                let row = odd_dimensions(n, self, 0, style, precision);
+                let row = odd_dimensions(n, self, 0, style, precision, summarize);
                 Table::init(vec![row]).with_style(style)
             } else {
-                even_dimensions(n, self, 0, style, precision)
+                even_dimensions(n, self, 0, style, precision, summarize)
             };
 
             write!(f, "{}", table)?;
         }
 
-        writeln!(f, "{:?}", self)
+        summary(self, f)?;
+        writeln!(f)
     }
 }
 
@@ -47,6 +65,7 @@ fn odd_dimensions<T>(
     stride_offset: usize,
     style: &TableFormat,
     precision: usize,
+    summarize: bool,
 ) -> Row
 where
     T: Copy + Display,
@@ -58,23 +77,30 @@ where
     if n == 1 {
         let offset = tensor.offset() + stride_offset;
         Row::from(
-            (0..size)
-                .map(|index| {
-                    let index = stride.offset(index, size) + offset;
-                    let element = tensor.data[index];
-                    let element = &format!("{:.precision$}", element);
-                    Cell::from(&element)
+            shown_indices(size, summarize)
+                .map(|index| match index {
+                    Some(index) => {
+                        let index = stride.offset(index, size) + offset;
+                        let element = tensor.data[index];
+                        let element = &format!("{:.precision$}", element);
+                        Cell::from(&element)
+                    }
+                    None => Cell::from(&"..."),
                 })
                 .collect::<Vec<Cell>>(),
         )
     } else {
         Row::from(
-            (0..size)
-                .map(|index| {
-                    let offset = stride.offset(index, size) + stride_offset;
-                    even_dimensions(n - 1, tensor, offset, style, precision)
+            shown_indices(size, summarize)
+                .map(|index| match index {
+                    Some(index) => {
+                        let offset = stride.offset(index, size) + stride_offset;
+                        even_dimensions(n - 1, tensor, offset, style, precision, summarize)
+                            .to_string()
+                    }
+                    None => "...".to_string(),
                 })
-                .collect::<Vec<Table>>(),
+                .collect::<Vec<String>>(),
         )
     }
 }
@@ -85,6 +111,7 @@ fn even_dimensions<T>(
     stride_offset: usize,
     style: &TableFormat,
     precision: usize,
+    summarize: bool,
 ) -> Table
 where
     T: Copy + Display,
@@ -93,12 +120,26 @@ where
     let size = tensor.sizes()[dim];
     let stride = tensor.strides()[dim];
 
-    let rows = (0..size)
-        .map(|index| {
-            let offset = stride.offset(index, size) + stride_offset;
-            odd_dimensions(n - 1, tensor, offset, style, precision)
+    let rows = shown_indices(size, summarize)
+        .map(|index| match index {
+            Some(index) => {
+                let offset = stride.offset(index, size) + stride_offset;
+                odd_dimensions(n - 1, tensor, offset, style, precision, summarize)
+            }
+            None => Row::from(vec!["..."]),
         })
         .collect();
 
     Table::init(rows).with_style(style)
 }
+
+/// Indices to print along a dimension, where `None` stands for the elided middle.
+fn shown_indices(size: usize, summarize: bool) -> Box<dyn Iterator<Item = Option<usize>>> {
+    if summarize && size > 2 * EDGE_ITEMS {
+        let head = (0..EDGE_ITEMS).map(Some);
+        let tail = (size - EDGE_ITEMS..size).map(Some);
+        Box::new(head.chain(std::iter::once(None)).chain(tail))
+    } else {
+        Box::new((0..size).map(Some))
+    }
+}
@@ -1,3 +1,6 @@
+//! Error types for tensor operations. Fallible methods return either one of these directly or
+//! a [`VenumError`] wrapping it.
+
 use thiserror::Error;
 
 // --- Misc ---
@@ -49,12 +52,84 @@ pub enum EmptyTensorError {
 pub struct NonContiguousError;
 
 #[derive(Error, Debug)]
-#[error("Size {size} cannot be expaned to size {expansion}. To be expanded, size should be 1.")]
+#[error("Size {size} cannot be expanded to size {expansion}. To be expanded, size should be 1.")]
 pub struct ExpansionError {
     pub size: usize,
     pub expansion: usize,
 }
 
+#[derive(Error, Debug)]
+#[error("Dimension {dimension} has size {size}. To be squeezed, size should be 1.")]
+pub struct SqueezeError {
+    pub dimension: usize,
+    pub size: usize,
+}
+
+#[derive(Error, Debug)]
+#[error("Size {size} cannot be tiled to size {target}. To be tiled, target should be a multiple of size.")]
+pub struct TileError {
+    pub size: usize,
+    pub target: usize,
+}
+
+#[derive(Error, Debug)]
+#[error("Size {size} cannot be padded to smaller size {target}.")]
+pub struct PadToError {
+    pub size: usize,
+    pub target: usize,
+}
+
+#[derive(Error, Debug)]
+pub enum CropError {
+    #[error(
+        "Expected one padding pair per dimension, got {padding} pairs for {dimensions} dimensions."
+    )]
+    Length { dimensions: usize, padding: usize },
+    #[error("Cannot crop {crop} elements from dimension {dimension} of size {size}.")]
+    Size {
+        dimension: usize,
+        size: usize,
+        crop: usize,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error("Size {size} cannot be split into {heads} heads. Size should be a multiple of the number of heads.")]
+pub struct HeadsError {
+    pub size: usize,
+    pub heads: usize,
+}
+
+#[derive(Error, Debug)]
+#[error("Slice step cannot be 0.")]
+pub struct StepError;
+
+#[derive(Error, Debug)]
+pub enum SplitError {
+    #[error("Split size should be greater than 0.")]
+    Size,
+
+    #[error("Number of chunks should be greater than 0.")]
+    Chunks,
+}
+
+#[derive(Error, Debug)]
+pub enum ConcatError {
+    #[error("No tensors to concatenate.")]
+    Empty,
+
+    #[error("Cannot concatenate a {ndims}d tensor with a {expected}d tensor.")]
+    Ndims { expected: usize, ndims: usize },
+
+    #[error("Cannot concatenate along dimension {concat_dimension}. Sizes differ at dimension {dimension}, {expected} != {size}.")]
+    Size {
+        concat_dimension: usize,
+        dimension: usize,
+        expected: usize,
+        size: usize,
+    },
+}
+
 #[derive(Error, Debug)]
 #[error("Current ndims ({current}) is greater than unsqueezed ndims ({unsqueezed}).")]
 pub struct UnsqueezeError {
@@ -63,16 +138,41 @@ pub struct UnsqueezeError {
 }
 
 #[derive(Error, Debug)]
-#[error("Shapes {lhs_sizes:?} and {rhs_sizes:?} cannot broadcasted together.")]
+#[error("Shapes {lhs_sizes:?} and {rhs_sizes:?} cannot be broadcast together.")]
 pub struct BroadcastError {
     pub lhs_sizes: Vec<usize>,
     pub rhs_sizes: Vec<usize>,
 }
 
+#[derive(Error, Debug)]
+#[error("Expected a {expected}d tensor, found a {ndims}d tensor.")]
+pub struct NdimsError {
+    pub expected: usize,
+    pub ndims: usize,
+}
+
+#[derive(Error, Debug)]
+#[error("Expected at least a {minimum}d tensor, found a {ndims}d tensor.")]
+pub struct MinNdimsError {
+    pub minimum: usize,
+    pub ndims: usize,
+}
+
 #[derive(Error, Debug)]
 #[error("Transpose requires at least two dimensions.")]
 pub struct TransposeError;
 
+#[derive(Error, Debug)]
+pub enum UnfoldError {
+    #[error(
+        "Window ({window}) should be non-zero and at most the size of the dimension ({size})."
+    )]
+    Window { window: usize, size: usize },
+
+    #[error("Step should be non-zero.")]
+    Step,
+}
+
 // --- Index, Range, Dims ---
 
 #[derive(Error, Debug)]
@@ -98,6 +198,9 @@ pub enum DimensionError {
 
     #[error("Dimension {0} repeats.")]
     Repetition(usize),
+
+    #[error("Axis {axis} is out of range for a {ndims}d tensor.")]
+    AxisOutOfRange { axis: isize, ndims: usize },
 }
 
 #[derive(Error, Debug)]
@@ -113,6 +216,42 @@ pub enum RangeError {
     GreaterStartRange(usize, usize),
 }
 
+#[derive(Error, Debug)]
+pub enum IndexTensorError {
+    #[error("Indices have {indices_ndims} dimensions, but the tensor has {tensor_ndims}.")]
+    Ndims {
+        indices_ndims: usize,
+        tensor_ndims: usize,
+    },
+
+    #[error("Indices size ({indices_size}) does not match tensor size ({tensor_size}) for dimension {dimension}.")]
+    Size {
+        dimension: usize,
+        indices_size: usize,
+        tensor_size: usize,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error("Shapes {lhs_sizes:?} and {rhs_sizes:?} do not match.")]
+pub struct ShapeMismatchError {
+    pub lhs_sizes: Vec<usize>,
+    pub rhs_sizes: Vec<usize>,
+}
+
+// --- Search ---
+
+#[derive(Error, Debug)]
+#[error("Bins should be monotonically increasing or decreasing.")]
+pub struct MonotonicError;
+
+#[derive(Error, Debug)]
+#[error("Delta degrees of freedom {ddof} must be less than the number of elements ({numel}).")]
+pub struct DdofError {
+    pub ddof: usize,
+    pub numel: usize,
+}
+
 // --- Matmul ---
 
 #[derive(Error, Debug)]
@@ -120,9 +259,18 @@ pub enum MatmulShapeError {
     #[error("Cannot matrix mutltiply with 0d tensor.")]
     Matmul0d,
 
+    #[error("Cannot matrix multiply 1d tensor with {ndims}d tensor. 1d tensors only pair with 1d or 2d tensors.")]
+    Matmul1dNd { ndims: usize },
+
+    #[error("Cannot take dot product. [n1] @ [n2], n1 ({n1}) != n2 ({n2}).")]
+    Matmul1d { n1: usize, n2: usize },
+
     #[error("Cannot be matrix multiplied. [m x n1] @ [n2 x l], n1 ({n1}) != n2 ({n2}).")]
     Matmul2d { n1: usize, n2: usize },
 
+    #[error("Cannot multiply matrix by vector. [m x n1] @ [n2], n1 ({n1}) != n2 ({n2}).")]
+    MatrixVector { n1: usize, n2: usize },
+
     #[error("Cannot be matrix multiplied. [m1 x n1] @ [m2 x n2 x l], n1 ({n1}) != n2 ({n2}).")]
     MatmulNd { n1: usize, n2: usize },
 }
@@ -137,3 +285,131 @@ pub struct ValidConvShapeError {
     pub input_sizes: Vec<usize>,
     pub kernel_sizes: Vec<usize>,
 }
+
+#[derive(Error, Debug)]
+pub enum ConvOptionsError {
+    #[error("Conv strides should be greater than 0.")]
+    Stride,
+
+    #[error("Dilation should be greater than 0.")]
+    Dilation,
+
+    #[error(
+        "Dilated kernel size ({kernel_size}) is larger than padded input size ({input_size})."
+    )]
+    KernelSize {
+        input_size: usize,
+        kernel_size: usize,
+    },
+}
+
+// --- NN ---
+
+#[cfg(feature = "rand")]
+#[derive(Error, Debug)]
+#[error("Dropout probability should be in the range [0, 1).")]
+pub struct DropoutError;
+
+// --- Crate error ---
+
+/// Error returned by every fallible tensor operation. Each variant wraps one of the error types
+/// above, so callers can `match` on the kind of failure and read its fields.
+#[derive(Error, Debug)]
+pub enum VenumError {
+    #[error(transparent)]
+    UsizeCast(#[from] UsizeCastError),
+
+    #[error(transparent)]
+    Phantom(#[from] PhantomError),
+
+    #[error(transparent)]
+    InvalidDataLength(#[from] InvalidDataLengthError),
+
+    #[error(transparent)]
+    InvalidReshape(#[from] ReshapeError),
+
+    #[error(transparent)]
+    EmptyTensor(#[from] EmptyTensorError),
+
+    #[error(transparent)]
+    NonContiguous(#[from] NonContiguousError),
+
+    #[error(transparent)]
+    Expansion(#[from] ExpansionError),
+
+    #[error(transparent)]
+    Squeeze(#[from] SqueezeError),
+
+    #[error(transparent)]
+    Tile(#[from] TileError),
+
+    #[error(transparent)]
+    PadTo(#[from] PadToError),
+
+    #[error(transparent)]
+    Crop(#[from] CropError),
+
+    #[error(transparent)]
+    Heads(#[from] HeadsError),
+
+    #[error(transparent)]
+    Step(#[from] StepError),
+
+    #[error(transparent)]
+    Split(#[from] SplitError),
+
+    #[error(transparent)]
+    Concat(#[from] ConcatError),
+
+    #[error(transparent)]
+    Unsqueeze(#[from] UnsqueezeError),
+
+    #[error(transparent)]
+    NonBroadcastable(#[from] BroadcastError),
+
+    #[error(transparent)]
+    Ndims(#[from] NdimsError),
+
+    #[error(transparent)]
+    MinNdims(#[from] MinNdimsError),
+
+    #[error(transparent)]
+    Transpose(#[from] TransposeError),
+
+    #[error(transparent)]
+    Unfold(#[from] UnfoldError),
+
+    #[error(transparent)]
+    OutOfBounds(#[from] IndexError),
+
+    #[error(transparent)]
+    Dimension(#[from] DimensionError),
+
+    #[error(transparent)]
+    Range(#[from] RangeError),
+
+    #[error(transparent)]
+    IndexTensor(#[from] IndexTensorError),
+
+    #[error(transparent)]
+    ShapeMismatch(#[from] ShapeMismatchError),
+
+    #[error(transparent)]
+    Monotonic(#[from] MonotonicError),
+
+    #[error(transparent)]
+    Ddof(#[from] DdofError),
+
+    #[error(transparent)]
+    MatmulShape(#[from] MatmulShapeError),
+
+    #[error(transparent)]
+    ValidConvShape(#[from] ValidConvShapeError),
+
+    #[error(transparent)]
+    ConvOptions(#[from] ConvOptionsError),
+
+    #[cfg(feature = "rand")]
+    #[error(transparent)]
+    Dropout(#[from] DropoutError),
+}
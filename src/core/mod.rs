@@ -1,10 +1,11 @@
 mod display;
-mod errors;
+pub mod errors;
 mod iters;
 mod ops;
 mod shape;
 mod tensor;
 mod tests;
 mod utils;
-pub use ops::conv;
-pub use tensor::Tensor;
+pub use ops::{conv, pad_sequence, sequence_mask, BorderMode, IndexMode, Reduction};
+pub use tensor::{PadAlign, Tensor};
+pub use utils::MaybeSendSync;
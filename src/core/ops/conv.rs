@@ -1,8 +1,14 @@
 use crate::{
-    core::{iters::Strider, shape::Shape, utils::Res},
+    core::{
+        errors::{ConvOptionsError, EmptyTensorError},
+        iters::Strider,
+        shape::Shape,
+        utils::{cast_usize, MaybeSendSync, Res},
+    },
     Tensor,
 };
-use std::{iter::Sum, ops::Mul};
+use num_traits::{FromPrimitive, Zero};
+use std::ops::{Div, Mul};
 
 pub enum Mode {
     Valid,
@@ -12,7 +18,7 @@ pub enum Mode {
 
 impl<T> Tensor<T>
 where
-    T: Copy + Mul<Output = T> + Sum<T> + Default,
+    T: Copy + Mul<Output = T> + Zero + MaybeSendSync,
 {
     pub fn correlate_1d(
         &self,
@@ -34,7 +40,7 @@ where
         let output_width = output_sizes[0];
 
         let sizes = [&self.shape.sizes[..i_first], &output_sizes].concat();
-        let mut data = vec![T::default(); sizes.iter().product()];
+        let mut data = vec![T::zero(); sizes.iter().product()];
 
         for iter_index in (0..output_width).step_by(strides[0]) {
             let product_sum = prod_sum_fn(
@@ -45,7 +51,7 @@ where
             )?;
 
             for (index, &value) in product_sum.data_contiguous().iter().enumerate() {
-                let offset = (index * output_width) + index;
+                let offset = (index * output_width) + iter_index / strides[0];
                 data[offset] = value
             }
         }
@@ -80,7 +86,7 @@ where
         let output_conv_product = output_conv_sizes[0] * output_conv_sizes[1];
 
         let sizes = [&self.shape.sizes[..input_conv_dims[0]], output_conv_sizes].concat();
-        let mut data = vec![T::default(); sizes.iter().product()];
+        let mut data = vec![T::zero(); sizes.iter().product()];
 
         for iter_index in Strider::new(output_conv_sizes, strides) {
             let product_sum = prod_sum_fn(
@@ -121,6 +127,204 @@ where
     }
 }
 
+/// Stride, zero padding and dilation of `conv2d`, each as `(height, width)`.
+pub struct Conv2dOptions {
+    pub stride: (usize, usize),
+    pub padding: (usize, usize),
+    pub dilation: (usize, usize),
+}
+
+impl Default for Conv2dOptions {
+    fn default() -> Self {
+        Conv2dOptions {
+            stride: (1, 1),
+            padding: (0, 0),
+            dilation: (1, 1),
+        }
+    }
+}
+
+/// Cross-correlates the last two dimensions of `input` with a 2d `kernel`. Each output size is
+/// `(input + 2 * padding - dilation * (kernel - 1) - 1) / stride + 1`.
+pub fn conv2d<T>(input: &Tensor<T>, kernel: &Tensor<T>, options: &Conv2dOptions) -> Res<Tensor<T>>
+where
+    T: Copy + Mul<Output = T> + Zero + MaybeSendSync,
+{
+    input.shape.valid_min_ndims(2)?;
+    kernel.shape.valid_ndims_exact(2)?;
+
+    let Conv2dOptions {
+        stride,
+        padding,
+        dilation,
+    } = *options;
+    if stride.0 == 0 || stride.1 == 0 {
+        return Err(ConvOptionsError::Stride.into());
+    } else if dilation.0 == 0 || dilation.1 == 0 {
+        return Err(ConvOptionsError::Dilation.into());
+    }
+
+    let n = input.ndims();
+    let padded = input.pad_dims(
+        T::zero(),
+        &[n - 2, n - 1],
+        &[(padding.0, padding.0), (padding.1, padding.1)],
+    )?;
+
+    let output_size = |input_size: usize, kernel_size: usize, stride: usize, dilation: usize| {
+        let kernel_size = dilation * kernel_size.saturating_sub(1) + 1;
+        match input_size.checked_sub(kernel_size) {
+            Some(difference) => Ok(difference / stride + 1),
+            None => Err(ConvOptionsError::KernelSize {
+                input_size,
+                kernel_size,
+            }),
+        }
+    };
+
+    let (height, width) = (padded.sizes()[n - 2], padded.sizes()[n - 1]);
+    let (kernel_height, kernel_width) = (kernel.sizes()[0], kernel.sizes()[1]);
+    let output_height = output_size(height, kernel_height, stride.0, dilation.0)?;
+    let output_width = output_size(width, kernel_width, stride.1, dilation.1)?;
+
+    let sizes = [&input.sizes()[..n - 2], &[output_height, output_width]].concat();
+    let mut data = vec![T::zero(); sizes.iter().product()];
+
+    // Each kernel element scales a strided view of the padded input.
+    for ky in 0..kernel_height {
+        for kx in 0..kernel_width {
+            let (y, x) = (ky * dilation.0, kx * dilation.1);
+            let mut ranges = vec![(0, 0, 1); n - 2];
+            ranges.push((y, y + (output_height - 1) * stride.0 + 1, stride.0 as isize));
+            ranges.push((x, x + (output_width - 1) * stride.1 + 1, stride.1 as isize));
+
+            let weight = kernel.idx(&[ky, kx]);
+            for (acc, elem) in data.iter_mut().zip(padded.slice_step(&ranges)?.data()) {
+                *acc = *acc + elem * weight;
+            }
+        }
+    }
+
+    Ok(Tensor::init(data, &sizes))
+}
+
+/// Cross-correlates a 2d `input` with a 2d `kernel`, as in image filtering. The input is zero
+/// padded for `mode`, so `Full` and `Same` reach past the edges, and every output element sums
+/// one kernel-sized window of the padded input. `Same` centres the kernel at `(kernel - 1) / 2`.
+pub fn correlate2d<T>(input: &Tensor<T>, kernel: &Tensor<T>, mode: Mode) -> Res<Tensor<T>>
+where
+    T: Copy + Mul<Output = T> + Zero + MaybeSendSync,
+{
+    input.shape.valid_ndims_exact(2)?;
+    kernel.shape.valid_ndims_exact(2)?;
+
+    let padding = kernel
+        .sizes()
+        .iter()
+        .map(|&kernel_size| {
+            let before = kernel_size.saturating_sub(1);
+            match mode {
+                Mode::Valid => (0, 0),
+                Mode::Full => (before, before),
+                Mode::Same => (before / 2, kernel_size / 2),
+            }
+        })
+        .collect::<Vec<(usize, usize)>>();
+
+    let (kernel_height, kernel_width) = (kernel.sizes()[0], kernel.sizes()[1]);
+    let windows = input
+        .pad(T::zero(), &padding)?
+        .unfold(0, kernel_height, 1)?
+        .unfold(1, kernel_width, 1)?;
+
+    (&windows * kernel)?.sum_dims(&[2, 3], false)
+}
+
+// --- Pooling ---
+
+/// Pooling over the last two dimensions, so `[N, C, H, W]` gives `[N, C, H_out, W_out]`.
+/// Windows that would run past the edge are dropped, so each output size is
+/// `(input - kernel) / stride + 1`, floored.
+impl<T> Tensor<T>
+where
+    T: Copy + MaybeSendSync,
+{
+    pub fn maxpool2d(&self, kernel: (usize, usize), stride: (usize, usize)) -> Res<Tensor<T>>
+    where
+        T: PartialOrd,
+    {
+        let (mut windows, sizes) = self.pool_windows(kernel, stride)?;
+        let mut data = windows.next().ok_or(EmptyTensorError::ReduceMax)??;
+
+        for window in windows {
+            for (max, elem) in data.iter_mut().zip(window?) {
+                if elem > *max {
+                    *max = elem;
+                }
+            }
+        }
+
+        Ok(Tensor::init(data, &sizes))
+    }
+
+    pub fn avgpool2d(&self, kernel: (usize, usize), stride: (usize, usize)) -> Res<Tensor<T>>
+    where
+        T: Zero + Div<Output = T> + FromPrimitive,
+    {
+        let (windows, sizes) = self.pool_windows(kernel, stride)?;
+        let count = cast_usize::<T>(kernel.0 * kernel.1)?;
+
+        let mut data = vec![T::zero(); sizes.iter().product()];
+        for window in windows {
+            for (sum, elem) in data.iter_mut().zip(window?) {
+                *sum = *sum + elem;
+            }
+        }
+
+        let data = data.into_iter().map(|sum| sum / count).collect();
+        Ok(Tensor::init(data, &sizes))
+    }
+
+    // Yields, for each kernel position, the strided view of the elements it covers.
+    fn pool_windows(
+        &self,
+        kernel: (usize, usize),
+        stride: (usize, usize),
+    ) -> Res<(impl Iterator<Item = Res<Vec<T>>> + '_, Vec<usize>)> {
+        self.shape.valid_min_ndims(2)?;
+        if stride.0 == 0 || stride.1 == 0 {
+            return Err(ConvOptionsError::Stride.into());
+        }
+
+        let n = self.ndims();
+        let output_size = |input_size: usize, kernel_size: usize, stride: usize| match input_size
+            .checked_sub(kernel_size)
+        {
+            Some(difference) if kernel_size > 0 => Ok(difference / stride + 1),
+            _ => Err(ConvOptionsError::KernelSize {
+                input_size,
+                kernel_size,
+            }),
+        };
+
+        let output_height = output_size(self.sizes()[n - 2], kernel.0, stride.0)?;
+        let output_width = output_size(self.sizes()[n - 1], kernel.1, stride.1)?;
+        let sizes = [&self.sizes()[..n - 2], &[output_height, output_width]].concat();
+
+        let windows = (0..kernel.0)
+            .flat_map(move |y| (0..kernel.1).map(move |x| (y, x)))
+            .map(move |(y, x)| {
+                let mut ranges = vec![(0, 0, 1); n - 2];
+                ranges.push((y, y + (output_height - 1) * stride.0 + 1, stride.0 as isize));
+                ranges.push((x, x + (output_width - 1) * stride.1 + 1, stride.1 as isize));
+
+                self.slice_step(&ranges).map(|window| window.data())
+            });
+
+        Ok((windows, sizes))
+    }
+}
+
 pub type ProductSumFn<T> = fn(
     (&Tensor<T>, &Tensor<T>),
     (&[usize], &[usize]),
@@ -155,7 +359,7 @@ impl Mode {
         kernel_sizes: &[usize],
     ) -> Res<ProductSumFn<T>>
     where
-        T: Copy + Mul<Output = T> + Sum<T>,
+        T: Copy + Mul<Output = T> + Zero + MaybeSendSync,
     {
         Ok(match self {
             Mode::Valid => {
@@ -177,7 +381,7 @@ impl Mode {
         indices: &[usize],
     ) -> Res<Tensor<T>>
     where
-        T: Copy + Mul<Output = T> + Sum<T>,
+        T: Copy + Mul<Output = T> + Zero + MaybeSendSync,
     {
         let ranges = indices
             .iter()
@@ -196,7 +400,7 @@ impl Mode {
         indices: &[usize],
     ) -> Res<Tensor<T>>
     where
-        T: Copy + Mul<Output = T> + Sum<T>,
+        T: Copy + Mul<Output = T> + Zero + MaybeSendSync,
     {
         let ranges = indices
             .iter()
@@ -221,7 +425,7 @@ impl Mode {
         indices: &[usize],
     ) -> Res<Tensor<T>>
     where
-        T: Copy + Mul<Output = T> + Sum<T>,
+        T: Copy + Mul<Output = T> + Zero + MaybeSendSync,
     {
         let input_ranges = indices
             .iter()
@@ -260,15 +464,16 @@ impl Mode {
         indices: &[usize],
     ) -> Res<Tensor<T>>
     where
-        T: Copy + Mul<Output = T> + Sum<T>,
+        T: Copy + Mul<Output = T> + Zero + MaybeSendSync,
     {
         let input_ranges = indices
             .iter()
             .zip(sizes.0)
             .zip(sizes.1)
             .map(|((&index, &input_size), &kernel_size)| {
-                let start = index.saturating_sub(1);
-                let end = (index + kernel_size - 1).min(input_size);
+                let center = (kernel_size - 1) / 2;
+                let start = index.saturating_sub(center);
+                let end = (index + kernel_size - center).min(input_size);
 
                 (start, end)
             })
@@ -279,8 +484,9 @@ impl Mode {
             .zip(sizes.0)
             .zip(sizes.1)
             .map(|((&index, &input_size), &kernel_size)| {
-                let start = 1_usize.saturating_sub(index);
-                let range = input_size - index.saturating_sub(1);
+                let center = (kernel_size - 1) / 2;
+                let start = center.saturating_sub(index);
+                let range = input_size - index.saturating_sub(center);
                 let end = kernel_size.min(start + range);
 
                 (start, end)
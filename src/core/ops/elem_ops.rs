@@ -1,13 +1,27 @@
-use crate::{core::utils::Res, Tensor};
-use std::ops::{Add, Div, Mul, Sub};
+use crate::{
+    core::{
+        iters::Indexer,
+        utils::{MaybeSendSync, Res},
+    },
+    Tensor,
+};
+use num_traits::Float;
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+    sync::Arc,
+};
 
 // --- Standard binary operations ---
 
+// Tensor-tensor operations broadcast through `zip` and tensor-scalar operations go through
+// `binary_map`. Outputs are `Res<Tensor<T>>`, so incompatible shapes return an error.
+
 macro_rules! binary_ops {
     ($trait:ident, $method:ident, $op:tt) => {
         impl<T> $trait for Tensor<T>
         where
-            T: Copy + $trait<Output = T>,
+            T: Copy + $trait<Output = T> + MaybeSendSync,
         {
             type Output = Res<Tensor<T>>;
             fn $method(self, rhs: Tensor<T>) -> Self::Output {
@@ -17,7 +31,7 @@ macro_rules! binary_ops {
 
         impl<T> $trait for &Tensor<T>
         where
-            T: Copy + $trait<Output = T>,
+            T: Copy + $trait<Output = T> + MaybeSendSync,
         {
             type Output = Res<Tensor<T>>;
             fn $method(self, rhs: &Tensor<T>) -> Self::Output {
@@ -27,7 +41,7 @@ macro_rules! binary_ops {
 
         impl<T> $trait<Tensor<T>> for &Tensor<T>
         where
-            T: Copy + $trait<Output = T>,
+            T: Copy + $trait<Output = T> + MaybeSendSync,
         {
             type Output = Res<Tensor<T>>;
             fn $method(self, rhs: Tensor<T>) -> Self::Output {
@@ -37,7 +51,7 @@ macro_rules! binary_ops {
 
         impl<T> $trait<&Tensor<T>> for Tensor<T>
         where
-            T: Copy + $trait<Output = T>,
+            T: Copy + $trait<Output = T> + MaybeSendSync,
         {
             type Output = Res<Tensor<T>>;
             fn $method(self, rhs: &Tensor<T>) -> Self::Output {
@@ -47,7 +61,7 @@ macro_rules! binary_ops {
 
         impl<T> $trait<T> for Tensor<T>
         where
-            T: Copy + $trait<Output = T>,
+            T: Copy + $trait<Output = T> + MaybeSendSync,
         {
             type Output = Res<Tensor<T>>;
             fn $method(self, rhs: T) -> Self::Output {
@@ -57,7 +71,7 @@ macro_rules! binary_ops {
 
         impl<T> $trait<T> for &Tensor<T>
         where
-            T: Copy + $trait<Output = T>,
+            T: Copy + $trait<Output = T> + MaybeSendSync,
         {
             type Output = Res<Tensor<T>>;
             fn $method(self, rhs: T) -> Self::Output {
@@ -72,6 +86,162 @@ binary_ops!(Sub, sub, -);
 binary_ops!(Mul, mul, *);
 binary_ops!(Div, div, /);
 
+// --- Comparison ---
+
+// Each comparison has a scalar form and a `_tensor` form that broadcasts through `zip`. The
+// equality variants are `eq_elem` and `ne_elem` so they don't shadow `PartialEq`.
+
+macro_rules! comparison_ops {
+    ($($method:ident, $tensor_method:ident, $op:tt;)*) => {
+        impl<T> Tensor<T>
+        where
+            T: Copy + PartialOrd + MaybeSendSync,
+        {
+            $(
+                pub fn $method(&self, rhs: T) -> Res<Tensor<bool>> {
+                    self.binary_map(rhs, |l, r| l $op r)
+                }
+
+                pub fn $tensor_method(&self, rhs: &Tensor<T>) -> Res<Tensor<bool>> {
+                    self.zip(rhs, |l, r| l $op r)
+                }
+            )*
+        }
+    };
+}
+
+comparison_ops! {
+    gt, gt_tensor, >;
+    ge, ge_tensor, >=;
+    lt, lt_tensor, <;
+    le, le_tensor, <=;
+    eq_elem, eq_elem_tensor, ==;
+    ne_elem, ne_elem_tensor, !=;
+}
+
+// --- Clamp, maximum and minimum ---
+
+impl<T> Tensor<T>
+where
+    T: Copy + PartialOrd + MaybeSendSync,
+{
+    pub fn clamp(&self, min: T, max: T) -> Res<Tensor<T>> {
+        self.unary_map(|elem| clamp(elem, min, max))
+    }
+
+    pub fn clamp_tensor(&self, min: &Tensor<T>, max: &Tensor<T>) -> Res<Tensor<T>> {
+        self.zip3(min, max, clamp)
+    }
+
+    pub fn clip_grad_value(&self, clip: T) -> Res<Tensor<T>>
+    where
+        T: Neg<Output = T>,
+    {
+        self.clamp(-clip, clip)
+    }
+
+    pub fn maximum(&self, other: &Tensor<T>) -> Res<Tensor<T>> {
+        self.zip(other, |l, r| if r > l { r } else { l })
+    }
+
+    pub fn minimum(&self, other: &Tensor<T>) -> Res<Tensor<T>> {
+        self.zip(other, |l, r| if r < l { r } else { l })
+    }
+}
+
+fn clamp<T: PartialOrd>(elem: T, min: T, max: T) -> T {
+    if elem < min {
+        min
+    } else if elem > max {
+        max
+    } else {
+        elem
+    }
+}
+
+// --- Interpolation ---
+
+impl<T> Tensor<T>
+where
+    T: Float + MaybeSendSync,
+{
+    pub fn lerp(&self, end: &Tensor<T>, weight: T) -> Res<Tensor<T>> {
+        self.zip(end, |start, end| start + weight * (end - start))
+    }
+
+    pub fn lerp_tensor(&self, end: &Tensor<T>, weight: &Tensor<T>) -> Res<Tensor<T>> {
+        self.zip3(end, weight, |start, end, weight| {
+            start + weight * (end - start)
+        })
+    }
+
+    /// Updates `self` in place to `decay * self + (1 - decay) * new`. Views whose elements
+    /// share storage, such as expanded tensors, are copied first so each element updates once.
+    pub fn ema_update(&mut self, new: &Tensor<T>, decay: T) -> Res<()> {
+        let new = new.broadcast_to(self.sizes())?;
+        if self.shape.is_overlapping() {
+            *self = self.to_contiguous()?;
+        }
+
+        let data = Arc::make_mut(&mut self.data);
+
+        for index in Indexer::new(&self.shape.sizes) {
+            let offset = self.shape.idx(&index);
+            data[offset] = decay * data[offset] + (T::one() - decay) * new.idx(&index);
+        }
+
+        Ok(())
+    }
+}
+
+// --- Approximate equality ---
+
+impl<T> Tensor<T>
+where
+    T: Float + MaybeSendSync,
+{
+    pub fn isclose(&self, other: &Tensor<T>, rtol: T, atol: T) -> Res<Tensor<bool>> {
+        self.zip(other, |l, r| (l - r).abs() <= atol + rtol * r.abs())
+    }
+
+    pub fn allclose(&self, other: &Tensor<T>, rtol: T, atol: T) -> Res<bool> {
+        Ok(self
+            .isclose(other, rtol, atol)?
+            .data()
+            .into_iter()
+            .all(|close| close))
+    }
+
+    pub fn approx_equal_report(
+        &self,
+        other: &Tensor<T>,
+        rtol: T,
+        atol: T,
+    ) -> Res<Result<(), String>>
+    where
+        T: Display,
+    {
+        if self.allclose(other, rtol, atol)? {
+            return Ok(Ok(()));
+        }
+
+        // The first element isclose rejects, so a NaN on either side counts as a mismatch.
+        let close = self.isclose(other, rtol, atol)?;
+        let pairs = self.zip(other, |l, r| (l, r))?;
+        let mismatch = Indexer::new(pairs.sizes())
+            .zip(close.data().into_iter().zip(pairs.data()))
+            .find(|(_, (close, _))| !close);
+
+        Ok(match mismatch {
+            Some((index, (_, (l, r)))) => Err(format!(
+                "Values differ at index {:?}: {} != {} (rtol: {}, atol: {}).",
+                index, l, r, rtol, atol
+            )),
+            None => Ok(()),
+        })
+    }
+}
+
 // --- Operations for floats ---
 
 impl Tensor<f32> {
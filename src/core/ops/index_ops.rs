@@ -0,0 +1,304 @@
+use crate::{
+    core::{
+        errors::{IndexError, IndexTensorError},
+        iters::Indexer,
+        shape::Shape,
+        utils::{MaybeSendSync, Res},
+    },
+    Tensor,
+};
+use num_traits::Zero;
+
+/// How positions that fall outside a dimension are resolved.
+pub enum BorderMode {
+    /// Wraps around to the other end of the dimension.
+    Wrap,
+    /// Clamps to the nearest edge element.
+    Clamp,
+    /// Reads zero.
+    Zero,
+}
+
+/// How indices past the end of the flattened tensor are resolved by [`Tensor::take`].
+pub enum IndexMode {
+    /// Returns an error.
+    Raise,
+    /// Wraps around modulo the number of elements.
+    Wrap,
+    /// Clips to the last element.
+    Clip,
+}
+
+impl<T> Tensor<T>
+where
+    T: Copy + MaybeSendSync,
+{
+    pub fn take_along_dim(&self, indices: &Tensor<usize>, dimension: usize) -> Res<Tensor<T>> {
+        self.shape.valid_dimensions(&[dimension])?;
+        self.shape.valid_index_sizes(indices.sizes(), dimension)?;
+
+        let data = Indexer::new(indices.sizes())
+            .map(|mut index| {
+                index[dimension] = indices.idx(&index);
+                Ok(self.data[self.shape.index(&index)?])
+            })
+            .collect::<Res<Vec<T>>>()?;
+
+        Ok(Tensor::init(data, indices.sizes()))
+    }
+
+    /// Reads elements of the row-major flattened tensor at `indices`, like NumPy's `take`
+    /// without an axis. The output has the shape of `indices`.
+    pub fn take(&self, indices: &Tensor<usize>, mode: IndexMode) -> Res<Tensor<T>> {
+        let data = self.data();
+        let size = data.len();
+
+        let values = indices
+            .data()
+            .into_iter()
+            .map(|index| {
+                let position = match mode {
+                    IndexMode::Wrap if size > 0 => index % size,
+                    IndexMode::Clip if size > 0 => index.min(size - 1),
+                    _ => index,
+                };
+
+                data.get(position).copied().ok_or(IndexError::OutOfRange {
+                    index,
+                    dimension: 0,
+                    size,
+                })
+            })
+            .collect::<Result<Vec<T>, IndexError>>()?;
+
+        Ok(Tensor::init(values, indices.sizes()))
+    }
+
+    /// Picks `indices` along `dimension` in the given order, allowing repeats, into a new
+    /// contiguous tensor with `indices.len()` along that dimension.
+    pub fn index_select(&self, dimension: usize, indices: &[usize]) -> Res<Tensor<T>> {
+        self.shape.valid_dimensions(&[dimension])?;
+        let size = self.sizes()[dimension];
+
+        let slices = indices
+            .iter()
+            .map(|&index| {
+                if index >= size {
+                    return Err(IndexError::OutOfRange {
+                        index,
+                        dimension,
+                        size,
+                    }
+                    .into());
+                }
+
+                self.single_slice(dimension, index)
+            })
+            .collect::<Res<Vec<Tensor<T>>>>()?;
+
+        if slices.is_empty() {
+            let mut sizes = self.sizes().to_vec();
+            sizes[dimension] = 0;
+
+            return Ok(Tensor::init(Vec::new(), &sizes));
+        }
+
+        Tensor::concat(&slices.iter().collect::<Vec<_>>(), dimension)
+    }
+
+    /// PyTorch-style alias of [`Tensor::take_along_dim`]. Each output element reads `self` at
+    /// the position given by `index` along `dimension`.
+    pub fn gather(&self, dimension: usize, index: &Tensor<usize>) -> Res<Tensor<T>> {
+        self.take_along_dim(index, dimension)
+    }
+
+    /// Inverse of [`Tensor::gather`], returning a copy with each element of `src` written at
+    /// the position given by `index` along `dimension`.
+    pub fn scatter(
+        &self,
+        dimension: usize,
+        index: &Tensor<usize>,
+        src: &Tensor<T>,
+    ) -> Res<Tensor<T>> {
+        self.put_along_dim(index, src, dimension)
+    }
+
+    /// Elements where `mask` is true, in row-major order. `mask` is broadcast to the shape of
+    /// the tensor.
+    pub fn masked_select(&self, mask: &Tensor<bool>) -> Res<Tensor<T>> {
+        let mask = mask.broadcast_to(self.sizes())?;
+
+        let data = Indexer::new(self.sizes())
+            .filter(|index| mask.idx(index))
+            .map(|index| self.idx(&index))
+            .collect::<Vec<T>>();
+        let length = data.len();
+
+        Ok(Tensor::init(data, &[length]))
+    }
+
+    /// Copy with `value` written where `mask` is true. `mask` is broadcast to the shape of the
+    /// tensor.
+    pub fn masked_fill(&self, mask: &Tensor<bool>, value: T) -> Res<Tensor<T>> {
+        let mask = mask.broadcast_to(self.sizes())?;
+
+        let data = Indexer::new(self.sizes())
+            .map(|index| {
+                if mask.idx(&index) {
+                    value
+                } else {
+                    self.idx(&index)
+                }
+            })
+            .collect();
+
+        Ok(Tensor::init(data, self.sizes()))
+    }
+
+    /// Picks from `lhs` where `condition` is true and from `rhs` otherwise, broadcasting all
+    /// three operands together.
+    pub fn where_(condition: &Tensor<bool>, lhs: &Tensor<T>, rhs: &Tensor<T>) -> Res<Tensor<T>> {
+        let sizes = Self::broadcast_shapes(&[condition.sizes(), lhs.sizes(), rhs.sizes()])?;
+
+        let condition = condition.broadcast_to(&sizes)?;
+        let (lhs, rhs) = (lhs.broadcast_to(&sizes)?, rhs.broadcast_to(&sizes)?);
+
+        let data = Indexer::new(&sizes)
+            .map(|index| {
+                if condition.idx(&index) {
+                    lhs.idx(&index)
+                } else {
+                    rhs.idx(&index)
+                }
+            })
+            .collect();
+
+        Ok(Tensor::init(data, &sizes))
+    }
+
+    /// Looks up rows of a `[num_embeddings, dim]` table, returning `indices.sizes()` with the
+    /// embedding dimension appended.
+    pub fn embedding(weight: &Tensor<T>, indices: &Tensor<usize>) -> Res<Tensor<T>> {
+        weight.shape.valid_ndims_exact(2)?;
+
+        let (num_embeddings, dim) = (weight.sizes()[0], weight.sizes()[1]);
+        let sizes = [indices.sizes(), &[dim]].concat();
+        let mut data = Vec::with_capacity(sizes.iter().product());
+
+        for index in indices.data() {
+            if index >= num_embeddings {
+                return Err(IndexError::OutOfRange {
+                    index,
+                    dimension: 0,
+                    size: num_embeddings,
+                }
+                .into());
+            }
+
+            data.extend(weight.select(0, index)?.data());
+        }
+
+        Ok(Tensor::init(data, &sizes))
+    }
+
+    /// Reads each element from its position shifted by the matching entry of `offsets` along
+    /// `dimension`. `offsets` is broadcast to the shape of the tensor.
+    pub fn gather_offsets(
+        &self,
+        offsets: &Tensor<isize>,
+        dimension: usize,
+        mode: BorderMode,
+    ) -> Res<Tensor<T>>
+    where
+        T: Zero,
+    {
+        self.shape.valid_dimensions(&[dimension])?;
+        let offsets = offsets.broadcast_to(self.sizes())?;
+
+        let size = self.sizes()[dimension] as isize;
+        let data = Indexer::new(self.sizes())
+            .map(|mut index| {
+                let position = index[dimension] as isize + offsets.idx(&index);
+                let position = match mode {
+                    BorderMode::Wrap => position.rem_euclid(size),
+                    BorderMode::Clamp => position.clamp(0, size - 1),
+                    BorderMode::Zero if !(0..size).contains(&position) => return T::zero(),
+                    BorderMode::Zero => position,
+                };
+
+                index[dimension] = position as usize;
+                self.idx(&index)
+            })
+            .collect();
+
+        Ok(Tensor::init(data, self.sizes()))
+    }
+
+    pub fn put_along_dim(
+        &self,
+        indices: &Tensor<usize>,
+        values: &Tensor<T>,
+        dimension: usize,
+    ) -> Res<Tensor<T>> {
+        self.shape.valid_dimensions(&[dimension])?;
+        self.shape.valid_index_sizes(indices.sizes(), dimension)?;
+        Shape::valid_matching(indices.sizes(), values.sizes())?;
+
+        let shape = Shape::new(self.sizes());
+        let mut data = self.data();
+
+        for index in Indexer::new(indices.sizes()) {
+            let mut target = index.clone();
+            target[dimension] = indices.idx(&index);
+
+            let offset = shape.index(&target)?;
+            data[offset] = values.idx(&index);
+        }
+
+        Ok(Tensor::init(data, self.sizes()))
+    }
+
+    pub fn nonzero(&self) -> Res<Tensor<usize>>
+    where
+        T: Zero,
+    {
+        let indices = Indexer::new(self.sizes())
+            .filter(|index| !self.idx(index).is_zero())
+            .collect::<Vec<Vec<usize>>>();
+
+        Ok(Tensor::init(
+            indices.concat(),
+            &[indices.len(), self.ndims()],
+        ))
+    }
+
+    pub fn from_coo(
+        indices: &Tensor<usize>,
+        values: &Tensor<T>,
+        sizes: &[usize],
+        default: T,
+    ) -> Res<Tensor<T>> {
+        indices.shape.valid_ndims_exact(2)?;
+
+        let (num_values, num_dimensions) = (indices.sizes()[0], indices.sizes()[1]);
+        if num_dimensions != sizes.len() {
+            return Err(IndexTensorError::Size {
+                dimension: 1,
+                indices_size: num_dimensions,
+                tensor_size: sizes.len(),
+            }
+            .into());
+        }
+        Shape::valid_matching(&[num_values], values.sizes())?;
+
+        let shape = Shape::new(sizes);
+        let mut data = vec![default; shape.numel()];
+
+        for (coordinates, value) in indices.data().chunks(num_dimensions).zip(values.data()) {
+            let offset = shape.index(coordinates)?;
+            data[offset] = value;
+        }
+
+        Ok(Tensor::init(data, sizes))
+    }
+}
@@ -1,22 +1,128 @@
 use crate::{
-    core::{errors::MatmulShapeError, iters::Slicer, shape::Shape, utils::Res},
+    core::{
+        errors::MatmulShapeError,
+        iters::{Indexer, Slicer},
+        shape::Shape,
+        utils::{MaybeSendSync, Res},
+    },
     Tensor,
 };
-use std::{iter::Sum, ops::Mul};
+use num_traits::Zero;
+use std::{ops::Mul, sync::Arc};
 
 impl<T> Tensor<T>
 where
-    T: Copy + Mul<Output = T> + Sum<T> + Default,
+    T: Copy + Mul<Output = T> + Zero + MaybeSendSync,
 {
+    /// Supported ranks are 1d @ 1d (dot product), 1d @ 2d and 2d @ 1d (the 1d side is treated
+    /// as a row or column vector and that dimension is dropped from the result), 2d @ 2d, and
+    /// batched nd @ nd with both sides at least 2d. Every other combination is an error.
     pub fn matmul(&self, rhs: &Tensor<T>) -> Res<Tensor<T>> {
         match (self.ndims(), rhs.ndims()) {
             (0, _) | (_, 0) => Err(MatmulShapeError::Matmul0d.into()),
-            (1, 1) => Tensor::scalar(self.mul(rhs)?.sum()?),
+            (1, 1) => self.matmul_1d(rhs),
+            (1, 2) => self.unsqueeze_dim(0)?.matmul_2d(rhs)?.squeeze_dim(0),
+            (2, 1) => self.matmul_2d(&rhs.unsqueeze_dim(1)?)?.squeeze_dim(1),
+            (1, ndims) | (ndims, 1) => Err(MatmulShapeError::Matmul1dNd { ndims }.into()),
             (2, 2) => self.matmul_2d(rhs),
             (_, _) => self.matmul_nd(rhs),
         }
     }
 
+    /// Computes `self @ rhs_tᵀ` for 2d tensors. Rows of both operands are contiguous in
+    /// row-major data, so each output element is a dot product over two contiguous slices.
+    pub fn matmul_nt(&self, rhs_t: &Tensor<T>) -> Res<Tensor<T>> {
+        self.shape.valid_ndims_exact(2)?;
+        rhs_t.shape.valid_ndims_exact(2)?;
+
+        let (m, n1) = (self.sizes()[0], self.sizes()[1]);
+        let (l, n2) = (rhs_t.sizes()[0], rhs_t.sizes()[1]);
+
+        if n1 != n2 {
+            return Err(MatmulShapeError::Matmul2d { n1, n2 }.into());
+        } else if n1 == 0 {
+            return Ok(Tensor::init(vec![T::zero(); m * l], &[m, l]));
+        }
+
+        let (lhs, rhs) = (self.data(), rhs_t.data());
+        let mut data = Vec::with_capacity(m * l);
+
+        for row in lhs.chunks_exact(n1) {
+            for column in rhs.chunks_exact(n1) {
+                let product_sum = row
+                    .iter()
+                    .zip(column)
+                    .fold(T::zero(), |acc, (&lhs_elem, &rhs_elem)| {
+                        acc + lhs_elem * rhs_elem
+                    });
+
+                data.push(product_sum);
+            }
+        }
+
+        Ok(Tensor::init(data, &[m, l]))
+    }
+
+    /// Dot product of two 1d tensors, returned as a scalar tensor.
+    pub fn dot(&self, rhs: &Tensor<T>) -> Res<Tensor<T>> {
+        self.shape.valid_ndims_exact(1)?;
+        rhs.shape.valid_ndims_exact(1)?;
+
+        self.matmul_1d(rhs)
+    }
+
+    /// Product of a 2d matrix and a 1d vector, returning a 1d tensor.
+    pub fn mv(&self, vector: &Tensor<T>) -> Res<Tensor<T>> {
+        self.shape.valid_ndims_exact(2)?;
+        vector.shape.valid_ndims_exact(1)?;
+
+        let (n1, n2) = (self.sizes()[1], vector.sizes()[0]);
+        if n1 != n2 {
+            return Err(MatmulShapeError::MatrixVector { n1, n2 }.into());
+        }
+
+        self.matmul_2d(&vector.unsqueeze_dim(1)?)?.squeeze_dim(1)
+    }
+
+    /// Outer product of two 1d tensors of lengths `m` and `n`, returning `[m, n]`.
+    pub fn outer(&self, rhs: &Tensor<T>) -> Res<Tensor<T>> {
+        self.shape.valid_ndims_exact(1)?;
+        rhs.shape.valid_ndims_exact(1)?;
+
+        &self.unsqueeze_dim(1)? * &rhs.unsqueeze_dim(0)?
+    }
+
+    /// Rank-1 update `self += alpha * x yᵀ` in place, for 2d `self` and 1d `x` and `y`.
+    /// Views whose elements share storage, such as expanded tensors, are copied first.
+    pub fn ger(&mut self, alpha: T, x: &Tensor<T>, y: &Tensor<T>) -> Res<()> {
+        self.shape.valid_ndims_exact(2)?;
+        x.shape.valid_ndims_exact(1)?;
+        y.shape.valid_ndims_exact(1)?;
+        Shape::valid_matching(self.sizes(), &[x.sizes()[0], y.sizes()[0]])?;
+
+        if self.shape.is_overlapping() {
+            *self = self.to_contiguous()?;
+        }
+
+        let data = Arc::make_mut(&mut self.data);
+        for index in Indexer::new(&self.shape.sizes) {
+            let offset = self.shape.idx(&index);
+            data[offset] = data[offset] + alpha * x.idx(&index[..1]) * y.idx(&index[1..]);
+        }
+
+        Ok(())
+    }
+
+    fn matmul_1d(&self, rhs: &Tensor<T>) -> Res<Tensor<T>> {
+        let (n1, n2) = (self.sizes()[0], rhs.sizes()[0]);
+
+        if n1 != n2 {
+            return Err(MatmulShapeError::Matmul1d { n1, n2 }.into());
+        }
+
+        Tensor::scalar(self.mul(rhs)?.sum()?)
+    }
+
     fn matmul_2d(&self, rhs: &Tensor<T>) -> Res<Tensor<T>> {
         let (n1, n2) = (self.sizes()[1], rhs.sizes()[0]);
 
@@ -80,7 +186,7 @@ where
         );
 
         let sizes = [broadcast.as_slice(), &[m, l]].concat();
-        let mut data = vec![T::default(); sizes.iter().product()];
+        let mut data = vec![T::zero(); sizes.iter().product()];
 
         for (li, lhs_slice) in lhs_iter.enumerate() {
             let row = &lhs.slicer(&lhs_slice)?;
@@ -1,4 +1,11 @@
 pub mod conv;
 mod elem_ops;
+mod index_ops;
 mod matmul;
+mod nn_ops;
+#[cfg(feature = "rayon")]
+mod par_ops;
 mod reduce_ops;
+mod search_ops;
+pub use index_ops::{BorderMode, IndexMode};
+pub use nn_ops::{pad_sequence, sequence_mask, Reduction};
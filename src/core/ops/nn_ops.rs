@@ -0,0 +1,279 @@
+use crate::{
+    core::{
+        errors::ConcatError,
+        shape::Shape,
+        utils::{cast_usize, MaybeSendSync, Res},
+    },
+    PadAlign, Tensor,
+};
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "rand")]
+use {crate::core::errors::DropoutError, rand::Rng};
+
+pub enum Reduction {
+    Mean,
+    Sum,
+    None,
+}
+
+impl<T> Tensor<T>
+where
+    T: Float + MaybeSendSync,
+{
+    pub fn log_softmax(&self, dimension: usize) -> Res<Tensor<T>> {
+        let logsumexp = self.logsumexp_dims(&[dimension], true)?;
+        self.zip(&logsumexp, |elem, lse| elem - lse)
+    }
+
+    pub fn softmax_backward(
+        grad_output: &Tensor<T>,
+        output: &Tensor<T>,
+        dimension: usize,
+    ) -> Res<Tensor<T>> {
+        let weighted = grad_output.zip(output, |grad, out| grad * out)?;
+        let weighted_sum = weighted.reduce(
+            &[dimension],
+            |slice| Ok(slice.data().into_iter().fold(T::zero(), |acc, e| acc + e)),
+            true,
+        )?;
+
+        output.zip3(grad_output, &weighted_sum, |out, grad, sum| {
+            out * (grad - sum)
+        })
+    }
+
+    pub fn cross_entropy(
+        logits: &Tensor<T>,
+        targets: &Tensor<usize>,
+        dimension: usize,
+    ) -> Res<Tensor<T>>
+    where
+        T: FromPrimitive,
+    {
+        let log_probabilities = logits.log_softmax(dimension)?;
+        let indices = targets.unsqueeze_dim(dimension)?;
+        let target_log_probabilities = log_probabilities.take_along_dim(&indices, dimension)?;
+
+        let count = cast_usize::<T>(target_log_probabilities.numel())?;
+        let sum = target_log_probabilities
+            .data()
+            .into_iter()
+            .fold(T::zero(), |acc, elem| acc + elem);
+
+        Tensor::scalar(-sum / count)
+    }
+
+    pub fn mse_loss(&self, target: &Tensor<T>, reduction: Reduction) -> Res<Tensor<T>>
+    where
+        T: FromPrimitive,
+    {
+        let loss = self.zip(target, |input, target| (input - target).powi(2))?;
+        reduction.apply(loss)
+    }
+
+    pub fn l1_loss(&self, target: &Tensor<T>, reduction: Reduction) -> Res<Tensor<T>>
+    where
+        T: FromPrimitive,
+    {
+        let loss = self.zip(target, |input, target| (input - target).abs())?;
+        reduction.apply(loss)
+    }
+
+    /// Zeroes each element with probability `p` and scales the rest by `1 / (1 - p)` while
+    /// `training`, and is the identity otherwise.
+    #[cfg(feature = "rand")]
+    pub fn dropout(&self, p: T, training: bool, rng: &mut impl Rng) -> Res<Tensor<T>> {
+        let probability = p.to_f64().filter(|p| (0.0..1.0).contains(p));
+        let probability = probability.ok_or(DropoutError)?;
+
+        if !training || probability == 0.0 {
+            return Ok(self.view_same());
+        }
+
+        let scale = (T::one() - p).recip();
+        let data = self
+            .data()
+            .into_iter()
+            .map(|elem| {
+                if rng.gen_bool(probability) {
+                    T::zero()
+                } else {
+                    elem * scale
+                }
+            })
+            .collect();
+
+        Ok(Tensor::init(data, self.sizes()))
+    }
+
+    /// Normalizes over `normalized_dims` to zero mean and unit (biased) variance, then scales by
+    /// `weight` and shifts by `bias`, both broadcast against the input.
+    pub fn layer_norm(
+        &self,
+        normalized_dims: &[usize],
+        weight: Option<&Tensor<T>>,
+        bias: Option<&Tensor<T>>,
+        eps: T,
+    ) -> Res<Tensor<T>>
+    where
+        T: FromPrimitive,
+    {
+        let mean = self.mean_dims(normalized_dims, true)?;
+        let centered = self.zip(&mean, |elem, mean| elem - mean)?;
+        let variance = centered
+            .unary_map(|elem| elem * elem)?
+            .mean_dims(normalized_dims, true)?;
+
+        let normalized = centered.zip(&variance, |elem, var| elem / (var + eps).sqrt())?;
+        let scaled = match weight {
+            Some(weight) => normalized.zip(weight, |elem, weight| elem * weight)?,
+            None => normalized,
+        };
+
+        match bias {
+            Some(bias) => scaled.zip(bias, |elem, bias| elem + bias),
+            None => Ok(scaled),
+        }
+    }
+
+    /// Inference-time batch normalization over an `[N, C, ...]` input, using per-channel running
+    /// statistics and optional affine parameters, all of size `[C]`.
+    pub fn batch_norm_infer(
+        &self,
+        running_mean: &Tensor<T>,
+        running_var: &Tensor<T>,
+        weight: Option<&Tensor<T>>,
+        bias: Option<&Tensor<T>>,
+        eps: T,
+    ) -> Res<Tensor<T>> {
+        self.shape.valid_min_ndims(2)?;
+
+        let channels = self.sizes()[1];
+        let mut sizes = vec![1; self.ndims() - 1];
+        sizes[0] = channels;
+
+        let per_channel = |parameter: &Tensor<T>| -> Res<Tensor<T>> {
+            Shape::valid_matching(&[channels], parameter.sizes())?;
+            parameter.view_else_reshape(&sizes)
+        };
+
+        let mean = per_channel(running_mean)?;
+        let std = per_channel(running_var)?.unary_map(|var| (var + eps).sqrt())?;
+        let normalized = self.zip3(&mean, &std, |elem, mean, std| (elem - mean) / std)?;
+
+        let scaled = match weight {
+            Some(weight) => normalized.zip(&per_channel(weight)?, |elem, weight| elem * weight)?,
+            None => normalized,
+        };
+
+        match bias {
+            Some(bias) => scaled.zip(&per_channel(bias)?, |elem, bias| elem + bias),
+            None => Ok(scaled),
+        }
+    }
+
+    /// Computes `softmax(query @ keyᵀ / sqrt(d)) @ value`, where `d` is the last size of `key`.
+    /// Positions where `mask` is `false` are filled with negative infinity before the softmax.
+    pub fn scaled_dot_product_attention(
+        query: &Tensor<T>,
+        key: &Tensor<T>,
+        value: &Tensor<T>,
+        mask: Option<&Tensor<bool>>,
+    ) -> Res<Tensor<T>>
+    where
+        T: FromPrimitive,
+    {
+        key.shape.valid_min_ndims(2)?;
+
+        let last = key.ndims() - 1;
+        let scale = cast_usize::<T>(key.sizes()[last])?.sqrt();
+        let scores = query
+            .matmul(&key.transpose(last - 1, last)?)?
+            .unary_map(|elem| elem / scale)?;
+
+        let scores = match mask {
+            Some(mask) => scores.zip_with(
+                mask,
+                |elem, keep| {
+                    if keep {
+                        elem
+                    } else {
+                        T::neg_infinity()
+                    }
+                },
+            )?,
+            None => scores,
+        };
+
+        let weights = scores
+            .log_softmax(scores.ndims() - 1)?
+            .unary_map(|elem| elem.exp())?;
+
+        weights.matmul(value)
+    }
+}
+
+impl Reduction {
+    fn apply<T>(&self, loss: Tensor<T>) -> Res<Tensor<T>>
+    where
+        T: Float + FromPrimitive + MaybeSendSync,
+    {
+        match self {
+            Reduction::Mean => Tensor::scalar(loss.mean()?),
+            Reduction::Sum => Tensor::scalar(loss.sum()?),
+            Reduction::None => Ok(loss),
+        }
+    }
+}
+
+/// Pads variable-length sequences of shape `[L, *]` with `padding_value` to the longest length
+/// and stacks them into `[B, L_max, *]`, or `[L_max, B, *]` when `batch_first` is false.
+/// Also returns the original lengths as a `[B]` tensor.
+pub fn pad_sequence<T>(
+    sequences: &[&Tensor<T>],
+    padding_value: T,
+    batch_first: bool,
+) -> Res<(Tensor<T>, Tensor<usize>)>
+where
+    T: Copy + MaybeSendSync,
+{
+    let lengths = sequences
+        .iter()
+        .map(|sequence| {
+            sequence.shape.valid_min_ndims(1)?;
+            Ok(sequence.sizes()[0])
+        })
+        .collect::<Res<Vec<usize>>>()?;
+    let max_length = lengths.iter().copied().max().ok_or(ConcatError::Empty)?;
+
+    let padded = sequences
+        .iter()
+        .map(|sequence| {
+            let mut sizes = sequence.sizes().to_vec();
+            sizes[0] = max_length;
+            sequence.pad_to(&sizes, padding_value, PadAlign::After)
+        })
+        .collect::<Res<Vec<Tensor<T>>>>()?;
+
+    let batch = Tensor::stack(&padded.iter().collect::<Vec<_>>(), 0)?;
+    let batch = if batch_first {
+        batch
+    } else {
+        batch.transpose(0, 1)?.into_contiguous()?
+    };
+
+    Ok((batch, Tensor::init(lengths, &[sequences.len()])))
+}
+
+/// Marks the valid, non-padded positions of each sequence, returning `lengths.sizes()` with a
+/// trailing dimension of `max_length`.
+pub fn sequence_mask(lengths: &Tensor<usize>, max_length: usize) -> Res<Tensor<bool>> {
+    let sizes = [lengths.sizes(), &[max_length]].concat();
+    let data = lengths
+        .data()
+        .into_iter()
+        .flat_map(|length| (0..max_length).map(move |position| position < length))
+        .collect();
+
+    Ok(Tensor::init(data, &sizes))
+}
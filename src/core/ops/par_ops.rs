@@ -0,0 +1,132 @@
+use crate::{
+    core::{
+        iters::Slicer,
+        shape::{Shape, Stride},
+        utils::Res,
+    },
+    Tensor,
+};
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// Contiguous tensors with more elements than this are processed in parallel.
+const PARALLEL_THRESHOLD: usize = 8192;
+
+/// Parallel branches of `unary_map`, `binary_map`, `equal_zip` and `reduce`, which call them for
+/// contiguous tensors above `PARALLEL_THRESHOLD` elements, plus a parallel map-reduce.
+impl<T> Tensor<T>
+where
+    T: Copy + Send + Sync,
+{
+    pub(crate) fn par_unary_map<R>(&self, f: impl Fn(T) -> R + Send + Sync) -> Tensor<R>
+    where
+        R: Send,
+    {
+        let data = self
+            .data_contiguous()
+            .par_iter()
+            .map(|&elem| f(elem))
+            .collect();
+        self.with_data(data)
+    }
+
+    pub(crate) fn par_binary_map<R>(&self, rhs: T, f: impl Fn(T, T) -> R + Send + Sync) -> Tensor<R>
+    where
+        R: Send,
+    {
+        let data = self
+            .data_contiguous()
+            .par_iter()
+            .map(|&elem| f(elem, rhs))
+            .collect();
+        self.with_data(data)
+    }
+
+    pub(crate) fn par_equal_zip<U, R>(
+        &self,
+        rhs: &Tensor<U>,
+        f: impl Fn(T, U) -> R + Send + Sync,
+    ) -> Tensor<R>
+    where
+        U: Copy + Send + Sync,
+        R: Send,
+    {
+        let data = self
+            .data_contiguous()
+            .par_iter()
+            .zip(rhs.data_contiguous())
+            .map(|(&lhs_elem, &rhs_elem)| f(lhs_elem, rhs_elem))
+            .collect();
+        self.with_data(data)
+    }
+
+    pub(crate) fn par_reduce_slices<R>(
+        &self,
+        dimensions: &[usize],
+        f: impl Fn(&Tensor<T>) -> Res<R> + Send + Sync,
+    ) -> Res<Vec<R>>
+    where
+        R: Send,
+    {
+        Slicer::new(&self.shape.sizes, dimensions, true)
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|index| f(&self.slicer(&index)?))
+            .collect()
+    }
+
+    /// Maps every element and merges the results in parallel. `merge` should be associative
+    /// with `identity` as its identity, since elements are grouped per thread before merging.
+    pub fn par_reduce<A>(
+        &self,
+        identity: A,
+        map: impl Fn(T) -> A + Send + Sync,
+        merge: impl Fn(A, A) -> A + Send + Sync,
+    ) -> A
+    where
+        A: Clone + Send + Sync,
+    {
+        let merge_all = |data: &[T]| {
+            data.par_iter()
+                .map(|&elem| map(elem))
+                .reduce(|| identity.clone(), &merge)
+        };
+
+        // Flipped contiguous data is stored in reverse, and merge need not be commutative.
+        match self.strides().first() {
+            Some(Stride::Positive(_)) if self.is_contiguous() => merge_all(self.data_contiguous()),
+            _ => merge_all(&self.data()),
+        }
+    }
+
+    /// Yields the slices along `dimension`, with that dimension removed, for concurrent
+    /// processing. The slices are views sharing the data of `self`.
+    pub fn par_iter_dim(
+        &self,
+        dimension: usize,
+    ) -> Res<impl IndexedParallelIterator<Item = Tensor<T>>> {
+        self.shape.valid_dimensions(&[dimension])?;
+
+        let slices = (0..self.sizes()[dimension])
+            .map(|index| self.select(dimension, index))
+            .collect::<Res<Vec<Tensor<T>>>>()?;
+
+        Ok(slices.into_par_iter())
+    }
+
+    pub(crate) fn parallelizable(&self) -> bool {
+        self.is_contiguous() && self.numel() > PARALLEL_THRESHOLD
+    }
+
+    // Mirrors the contiguous branch of the serial maps, which keeps the strides.
+    fn with_data<R>(&self, data: Vec<R>) -> Tensor<R> {
+        Tensor {
+            data: Arc::new(data),
+            shape: Shape {
+                sizes: self.sizes().to_vec(),
+                strides: self.strides().to_vec(),
+                offset: 0,
+            },
+        }
+    }
+}
@@ -1,31 +1,34 @@
 use crate::{
     core::{
-        errors::EmptyTensorError,
+        errors::{DdofError, EmptyTensorError},
         iters::Indexer,
-        utils::{cast_usize, Res},
+        shape::Shape,
+        utils::{cast_usize, MaybeSendSync, Res},
     },
     Tensor,
 };
-use num_traits::FromPrimitive;
+use num_traits::{Float, FromPrimitive, One, Zero};
 use std::{
-    iter::{Product, Sum},
-    ops::Div,
+    iter::Product,
+    ops::{Add, Div, Mul},
 };
 
 impl<T> Tensor<T>
 where
-    T: Copy,
+    T: Copy + MaybeSendSync,
 {
     pub fn sum(&self) -> Res<T>
     where
-        T: Sum<T>,
+        T: Zero,
     {
         let sum = if self.is_contiguous() {
-            self.data_contiguous().iter().copied().sum()
+            self.data_contiguous()
+                .iter()
+                .fold(T::zero(), |acc, &elem| acc + elem)
         } else {
             Indexer::new(&self.shape.sizes)
                 .map(|index| self.idx(&index))
-                .sum()
+                .fold(T::zero(), |acc, elem| acc + elem)
         };
 
         Ok(sum)
@@ -33,7 +36,7 @@ where
 
     pub fn mean(&self) -> Res<T>
     where
-        T: Sum<T> + Div<T, Output = T> + FromPrimitive,
+        T: Zero + Div<T, Output = T> + FromPrimitive,
     {
         let numel = self.numel();
         let numel_casted = cast_usize(numel)?;
@@ -56,46 +59,143 @@ where
         Ok(product)
     }
 
-    pub fn max(&self) -> Res<T>
+    pub fn sum_as<A>(&self) -> Res<A>
     where
-        T: Ord,
+        T: Into<A>,
+        A: Zero,
     {
-        let max = if self.is_contiguous() {
-            self.data_contiguous().iter().copied().max()
-        } else {
-            Indexer::new(&self.shape.sizes)
-                .map(|index| self.idx(&index))
-                .max()
-        };
+        Ok(self
+            .data()
+            .into_iter()
+            .fold(A::zero(), |acc, elem| acc + elem.into()))
+    }
 
+    pub fn product_as<A>(&self) -> Res<A>
+    where
+        T: Into<A>,
+        A: One,
+    {
+        Ok(self
+            .data()
+            .into_iter()
+            .fold(A::one(), |acc, elem| acc * elem.into()))
+    }
+
+    /// Largest element. `NaN` and other unordered values propagate: if any element is
+    /// unordered, the first one is returned.
+    pub fn max(&self) -> Res<T>
+    where
+        T: PartialOrd,
+    {
+        let max = self.fold_extreme(|elem, acc| elem > acc);
         max.ok_or(EmptyTensorError::ReduceMax.into())
     }
 
+    /// Smallest element, propagating unordered values like [`Tensor::max`].
     pub fn min(&self) -> Res<T>
     where
-        T: Ord,
+        T: PartialOrd,
     {
-        let min = if self.is_contiguous() {
-            self.data_contiguous().iter().copied().min()
+        let min = self.fold_extreme(|elem, acc| elem < acc);
+        min.ok_or(EmptyTensorError::ReduceMin.into())
+    }
+
+    fn fold_extreme(&self, replaces: impl Fn(T, T) -> bool) -> Option<T>
+    where
+        T: PartialOrd,
+    {
+        let unordered = |elem: T| elem.partial_cmp(&elem).is_none();
+        let fold = |acc: T, elem: T| {
+            if !unordered(acc) && (unordered(elem) || replaces(elem, acc)) {
+                elem
+            } else {
+                acc
+            }
+        };
+
+        if self.is_contiguous() {
+            self.data_contiguous().iter().copied().reduce(fold)
         } else {
             Indexer::new(&self.shape.sizes)
                 .map(|index| self.idx(&index))
-                .min()
+                .reduce(fold)
+        }
+    }
+
+    pub fn moving_average(&self, window: usize, dimension: usize) -> Res<Tensor<T>>
+    where
+        T: Zero + Div<T, Output = T> + FromPrimitive,
+    {
+        let windows = self.unfold(dimension, window, 1)?;
+        let last = windows.ndims() - 1;
+
+        windows.mean_dims(&[last], true)?.squeeze_dim(last)
+    }
+
+    /// Running sum along `dimension`, accumulated from the end when `reverse` is set.
+    pub fn cumsum(&self, dimension: usize, reverse: bool) -> Res<Tensor<T>>
+    where
+        T: Add<Output = T>,
+    {
+        self.cumulative(dimension, reverse, |acc, elem| acc + elem)
+    }
+
+    /// Running product along `dimension`, accumulated from the end when `reverse` is set.
+    pub fn cumprod(&self, dimension: usize, reverse: bool) -> Res<Tensor<T>>
+    where
+        T: Mul<Output = T>,
+    {
+        self.cumulative(dimension, reverse, |acc, elem| acc * elem)
+    }
+
+    // Element `i` along `dimension` folds elements `0..=i` with `f`, or `i..` when `reverse`.
+    fn cumulative(&self, dimension: usize, reverse: bool, f: impl Fn(T, T) -> T) -> Res<Tensor<T>> {
+        self.shape.valid_dimensions(&[dimension])?;
+        let size = self.sizes()[dimension];
+        if size <= 1 {
+            return Ok(self.view_same());
+        }
+
+        // Distance between neighbours along `dimension` in the row-major output.
+        let step = self.sizes()[dimension + 1..].iter().product::<usize>();
+        let mut indices = Indexer::new(self.sizes()).collect::<Vec<_>>();
+        let first = if reverse {
+            indices.reverse();
+            size - 1
+        } else {
+            0
         };
 
-        min.ok_or(EmptyTensorError::ReduceMin.into())
+        // Built in iteration order, so reversed runs are flipped back at the end.
+        let mut data = Vec::with_capacity(self.numel());
+        for (position, index) in indices.into_iter().enumerate() {
+            let elem = self.idx(&index);
+            let value = if index[dimension] == first {
+                elem
+            } else {
+                f(data[position - step], elem)
+            };
+
+            data.push(value);
+        }
+
+        if reverse {
+            data.reverse();
+        }
+
+        Ok(Tensor::init(data, self.sizes()))
     }
 
     pub fn sum_dims(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<T>>
     where
-        T: Sum<T>,
+        T: Zero,
     {
         self.reduce(dimensions, Tensor::sum, keepdims)
     }
 
     pub fn mean_dims(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<T>>
     where
-        T: Sum<T> + Div<T, Output = T> + FromPrimitive,
+        T: Zero + Div<T, Output = T> + FromPrimitive,
     {
         self.reduce(dimensions, Tensor::mean, keepdims)
     }
@@ -109,15 +209,291 @@ where
 
     pub fn max_dims(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<T>>
     where
-        T: Ord,
+        T: PartialOrd,
     {
         self.reduce(dimensions, Tensor::max, keepdims)
     }
 
     pub fn min_dims(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<T>>
     where
-        T: Ord,
+        T: PartialOrd,
     {
         self.reduce(dimensions, Tensor::min, keepdims)
     }
+
+    pub fn argmax(&self, dimension: usize, keepdims: bool) -> Res<Tensor<usize>>
+    where
+        T: PartialOrd,
+    {
+        self.arg_extreme(
+            dimension,
+            keepdims,
+            |elem, best| elem > best,
+            EmptyTensorError::ReduceMax,
+        )
+    }
+
+    pub fn argmin(&self, dimension: usize, keepdims: bool) -> Res<Tensor<usize>>
+    where
+        T: PartialOrd,
+    {
+        self.arg_extreme(
+            dimension,
+            keepdims,
+            |elem, best| elem < best,
+            EmptyTensorError::ReduceMin,
+        )
+    }
+
+    // Only a strictly better element replaces the current one, so ties resolve to the lowest index.
+    fn arg_extreme(
+        &self,
+        dimension: usize,
+        keepdims: bool,
+        better: impl Fn(T, T) -> bool,
+        empty: EmptyTensorError,
+    ) -> Res<Tensor<usize>> {
+        self.shape.valid_dimensions(&[dimension])?;
+        if self.sizes()[dimension] == 0 {
+            return Err(empty.into());
+        }
+
+        let (_, indices) = self.reduce_with_index(
+            &[dimension],
+            |slice| {
+                let extreme = slice.data().into_iter().enumerate().reduce(|best, elem| {
+                    if better(elem.1, best.1) {
+                        elem
+                    } else {
+                        best
+                    }
+                });
+
+                let (index, elem) = extreme.ok_or(EmptyTensorError::Slice)?;
+                Ok((elem, index))
+            },
+            keepdims,
+        )?;
+
+        Ok(indices)
+    }
+}
+
+// --- Reductions for floats ---
+
+impl<T> Tensor<T>
+where
+    T: Float + MaybeSendSync,
+{
+    pub fn sum_kahan(&self) -> Res<T> {
+        let (sum, _) =
+            self.data()
+                .into_iter()
+                .fold((T::zero(), T::zero()), |(sum, compensation), elem| {
+                    let compensated = elem - compensation;
+                    let total = sum + compensated;
+
+                    (total, (total - sum) - compensated)
+                });
+
+        Ok(sum)
+    }
+
+    pub fn sum_kahan_dims(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<T>> {
+        self.reduce(dimensions, Tensor::sum_kahan, keepdims)
+    }
+
+    pub fn logsumexp(&self) -> Res<T> {
+        let data = self.data();
+        let max = data
+            .iter()
+            .copied()
+            .reduce(T::max)
+            .ok_or(EmptyTensorError::ReduceMax)?;
+
+        if max.is_infinite() {
+            return Ok(max);
+        }
+
+        let sum = data
+            .into_iter()
+            .fold(T::zero(), |acc, elem| acc + (elem - max).exp());
+
+        Ok(max + sum.ln())
+    }
+
+    pub fn logsumexp_dims(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<T>> {
+        self.reduce(dimensions, Tensor::logsumexp, keepdims)
+    }
+
+    /// Integrates along `dimension` with the trapezoidal rule, for samples spaced `dx` apart. The
+    /// integrated dimension is removed from the result.
+    pub fn trapz(&self, dx: T, dimension: usize) -> Res<Tensor<T>> {
+        let half = dx / (T::one() + T::one());
+
+        self.reduce(
+            &[dimension],
+            |slice| {
+                let y = slice.data();
+                Ok(y.windows(2)
+                    .fold(T::zero(), |acc, pair| acc + (pair[0] + pair[1]) * half))
+            },
+            false,
+        )
+    }
+
+    /// Like [`Tensor::trapz`], with the sample coordinates along `dimension` given by the 1d `x`.
+    pub fn trapz_x(&self, x: &Tensor<T>, dimension: usize) -> Res<Tensor<T>> {
+        self.shape.valid_dimensions(&[dimension])?;
+        x.shape.valid_ndims_exact(1)?;
+        Shape::valid_matching(&[self.sizes()[dimension]], x.sizes())?;
+
+        let x = x.data();
+        let two = T::one() + T::one();
+
+        self.reduce(
+            &[dimension],
+            |slice| {
+                let y = slice.data();
+                Ok(y.windows(2)
+                    .zip(x.windows(2))
+                    .fold(T::zero(), |acc, (y_pair, x_pair)| {
+                        acc + (x_pair[1] - x_pair[0]) * (y_pair[0] + y_pair[1]) / two
+                    }))
+            },
+            false,
+        )
+    }
+
+    /// Variance over `dimensions`, dividing by `n - ddof`. A `ddof` of 0 gives the population
+    /// variance and 1 the sample variance.
+    pub fn var(&self, dimensions: &[usize], keepdims: bool, ddof: usize) -> Res<Tensor<T>>
+    where
+        T: FromPrimitive,
+    {
+        self.reduce(dimensions, |slice| slice.variance(ddof), keepdims)
+    }
+
+    /// Standard deviation over `dimensions`, the square root of [`Tensor::var`].
+    pub fn std(&self, dimensions: &[usize], keepdims: bool, ddof: usize) -> Res<Tensor<T>>
+    where
+        T: FromPrimitive,
+    {
+        self.reduce(
+            dimensions,
+            |slice| Ok(slice.variance(ddof)?.sqrt()),
+            keepdims,
+        )
+    }
+
+    fn variance(&self, ddof: usize) -> Res<T>
+    where
+        T: FromPrimitive,
+    {
+        let numel = self.numel();
+        let divisor = numel
+            .checked_sub(ddof)
+            .filter(|&divisor| divisor > 0)
+            .ok_or(DdofError { ddof, numel })?;
+
+        let mean = self.mean()?;
+        let squares = self
+            .data()
+            .into_iter()
+            .fold(T::zero(), |acc, elem| acc + (elem - mean).powi(2));
+
+        Ok(squares / cast_usize(divisor)?)
+    }
+
+    pub fn nansum(&self) -> Res<T> {
+        Ok(self
+            .non_nan()
+            .into_iter()
+            .fold(T::zero(), |acc, elem| acc + elem))
+    }
+
+    pub fn nanmean(&self) -> Res<T>
+    where
+        T: FromPrimitive,
+    {
+        let non_nan = self.non_nan();
+        let count_casted = cast_usize::<T>(non_nan.len())?;
+        let sum = non_nan.into_iter().fold(T::zero(), |acc, elem| acc + elem);
+
+        Ok(sum / count_casted)
+    }
+
+    pub fn nanmax(&self) -> Res<T> {
+        let max = self.non_nan().into_iter().reduce(T::max);
+        max.ok_or(EmptyTensorError::ReduceMax.into())
+    }
+
+    pub fn nanmin(&self) -> Res<T> {
+        let min = self.non_nan().into_iter().reduce(T::min);
+        min.ok_or(EmptyTensorError::ReduceMin.into())
+    }
+
+    pub fn nansum_dims(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<T>> {
+        self.reduce(dimensions, Tensor::nansum, keepdims)
+    }
+
+    pub fn nanmean_dims(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<T>>
+    where
+        T: FromPrimitive,
+    {
+        self.reduce(dimensions, Tensor::nanmean, keepdims)
+    }
+
+    pub fn nanmax_dims(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<T>> {
+        self.reduce(dimensions, Tensor::nanmax, keepdims)
+    }
+
+    pub fn nanmin_dims(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<T>> {
+        self.reduce(dimensions, Tensor::nanmin, keepdims)
+    }
+
+    fn non_nan(&self) -> Vec<T> {
+        self.data()
+            .into_iter()
+            .filter(|elem| !elem.is_nan())
+            .collect()
+    }
+}
+
+// --- Reductions for booleans ---
+
+impl Tensor<bool> {
+    pub fn all_true(&self) -> bool {
+        if self.is_contiguous() {
+            self.data_contiguous().iter().all(|&elem| elem)
+        } else {
+            Indexer::new(&self.shape.sizes).all(|index| self.idx(&index))
+        }
+    }
+
+    pub fn any_true(&self) -> bool {
+        if self.is_contiguous() {
+            self.data_contiguous().iter().any(|&elem| elem)
+        } else {
+            Indexer::new(&self.shape.sizes).any(|index| self.idx(&index))
+        }
+    }
+
+    /// Whether every element along `dimensions` is true. An empty `dimensions` checks the whole
+    /// tensor and returns a 0-d scalar, whatever `keepdims` is.
+    pub fn all(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<bool>> {
+        if dimensions.is_empty() {
+            return Ok(Tensor::init(vec![self.all_true()], &[]));
+        }
+        self.reduce(dimensions, |slice| Ok(slice.all_true()), keepdims)
+    }
+
+    /// Whether any element along `dimensions` is true. An empty `dimensions` checks the whole
+    /// tensor and returns a 0-d scalar, whatever `keepdims` is.
+    pub fn any(&self, dimensions: &[usize], keepdims: bool) -> Res<Tensor<bool>> {
+        if dimensions.is_empty() {
+            return Ok(Tensor::init(vec![self.any_true()], &[]));
+        }
+        self.reduce(dimensions, |slice| Ok(slice.any_true()), keepdims)
+    }
 }
@@ -0,0 +1,123 @@
+use crate::{
+    core::{
+        errors::MonotonicError,
+        iters::Slicer,
+        utils::{MaybeSendSync, Res},
+    },
+    Tensor,
+};
+use std::cmp::Ordering;
+
+impl<T> Tensor<T>
+where
+    T: Copy + PartialOrd + MaybeSendSync,
+{
+    pub fn searchsorted(&self, values: &Tensor<T>, right: bool) -> Res<Tensor<usize>> {
+        self.shape.valid_ndims_exact(1)?;
+
+        let sorted = self.data();
+        let search_fn: fn(&[T], T) -> usize = if right {
+            |sorted, value| sorted.partition_point(|&elem| elem <= value)
+        } else {
+            |sorted, value| sorted.partition_point(|&elem| elem < value)
+        };
+
+        values.unary_map(|value| search_fn(&sorted, value))
+    }
+
+    pub fn digitize(&self, bins: &Tensor<T>, right: bool) -> Res<Tensor<usize>> {
+        bins.shape.valid_ndims_exact(1)?;
+
+        let edges = bins.data();
+        if edges.windows(2).all(|pair| pair[0] <= pair[1]) {
+            bins.searchsorted(self, !right)
+        } else if edges.windows(2).all(|pair| pair[0] >= pair[1]) {
+            let length = edges.len();
+            let indices = bins.flip(&[0])?.searchsorted(self, !right)?;
+
+            indices.unary_map(|index| length - index)
+        } else {
+            Err(MonotonicError.into())
+        }
+    }
+
+    pub fn bucketize(&self, boundaries: &Tensor<T>, right: bool) -> Res<Tensor<usize>> {
+        boundaries.shape.valid_ndims_exact(1)?;
+
+        if boundaries.data().windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err(MonotonicError.into());
+        }
+
+        boundaries.searchsorted(self, right)
+    }
+
+    /// Sorts each 1d slice along `dimension` into a new contiguous tensor.
+    /// A `stable` sort keeps equal elements in their original order, otherwise a faster
+    /// unstable sort is used. Unordered values such as `NaN` are placed at the end in both
+    /// directions.
+    pub fn sort(&self, dimension: usize, descending: bool, stable: bool) -> Res<Tensor<T>> {
+        self.sort_map(dimension, descending, stable, |lane, index| lane[index])
+    }
+
+    /// Indices along `dimension` that would sort the tensor, with the same
+    /// ordering as [`Tensor::sort`].
+    pub fn argsort(&self, dimension: usize, descending: bool, stable: bool) -> Res<Tensor<usize>> {
+        self.sort_map(dimension, descending, stable, |_, index| index)
+    }
+
+    fn sort_map<R: Copy + MaybeSendSync>(
+        &self,
+        dimension: usize,
+        descending: bool,
+        stable: bool,
+        f: impl Fn(&[T], usize) -> R + MaybeSendSync,
+    ) -> Res<Tensor<R>> {
+        self.shape.valid_dimensions(&[dimension])?;
+
+        let length = self.sizes()[dimension];
+        let step = self.sizes()[dimension + 1..].iter().product::<usize>();
+
+        let lanes = Slicer::new(self.sizes(), &[dimension], true)
+            .map(|index| {
+                let lane = self.slicer(&index)?.data();
+                let mut order = (0..length).collect::<Vec<_>>();
+                let compare =
+                    |&a: &usize, &b: &usize| compare_unordered_last(&lane[a], &lane[b], descending);
+
+                if stable {
+                    order.sort_by(compare);
+                } else {
+                    order.sort_unstable_by(compare);
+                }
+
+                Ok((lane, order))
+            })
+            .collect::<Res<Vec<_>>>()?;
+
+        let data = (0..self.numel())
+            .map(|position| {
+                let (outer, rank, inner) = (
+                    position / (length * step),
+                    position / step % length,
+                    position % step,
+                );
+                let (lane, order) = &lanes[outer * step + inner];
+
+                f(lane, order[rank])
+            })
+            .collect();
+
+        Ok(Tensor::init(data, self.sizes()))
+    }
+}
+
+fn compare_unordered_last<T: PartialOrd>(a: &T, b: &T, descending: bool) -> Ordering {
+    match a.partial_cmp(b) {
+        Some(ordering) if descending => ordering.reverse(),
+        Some(ordering) => ordering,
+        None => {
+            let unordered = |elem: &T| elem.partial_cmp(elem).is_none();
+            unordered(a).cmp(&unordered(b))
+        }
+    }
+}
@@ -2,7 +2,7 @@ use crate::core::{errors::*, utils::Res};
 use std::{
     cmp::{max, Ordering},
     collections::HashSet,
-    iter::repeat,
+    iter::repeat_n,
     ops::Mul,
 };
 
@@ -13,7 +13,7 @@ pub(crate) struct Shape {
     pub offset: usize,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub enum Stride {
     Positive(usize),
     Negative(usize),
@@ -99,6 +99,8 @@ impl Shape {
         if ndims < 2 {
             return Err(TransposeError.into());
         }
+        self.valid_dimensions(&[dim_1])?;
+        self.valid_dimensions(&[dim_2])?;
 
         let mut permutation = Vec::from_iter(0..ndims);
         permutation.swap(dim_1, dim_2);
@@ -185,6 +187,68 @@ impl Shape {
         })
     }
 
+    pub(crate) fn squeeze_all_but(&self, keep: &[usize]) -> Res<Shape> {
+        self.valid_dimensions(keep)?;
+
+        let (sizes, strides): (Vec<usize>, Vec<Stride>) = self
+            .sizes
+            .iter()
+            .zip(&self.strides)
+            .enumerate()
+            .filter_map(|(d, (&size, &stride))| {
+                (size != 1 || keep.contains(&d)).then_some((size, stride))
+            })
+            .collect();
+
+        if sizes.is_empty() {
+            return Ok(self.squeeze()?);
+        }
+
+        Ok(Shape {
+            sizes,
+            strides,
+            offset: self.offset,
+        })
+    }
+
+    pub(crate) fn squeeze_dim(&self, dimension: usize) -> Res<Shape> {
+        self.valid_dimensions(&[dimension])?;
+
+        let size = self.sizes[dimension];
+        if size != 1 {
+            return Err(SqueezeError { dimension, size }.into());
+        } else if self.ndims() == 1 {
+            return Ok(self.clone());
+        }
+
+        let mut shape = self.clone();
+        shape.sizes.remove(dimension);
+        shape.strides.remove(dimension);
+
+        Ok(shape)
+    }
+
+    pub(crate) fn unsqueeze_dim(&self, dimension: usize) -> Result<Shape, DimensionError> {
+        let ndims = self.ndims();
+        if dimension > ndims {
+            return Err(DimensionError::OutOfRange {
+                dimension,
+                dim_range: ndims,
+            });
+        }
+
+        let stride = match (self.strides.get(dimension), self.sizes.get(dimension)) {
+            (Some(&stride), Some(&size)) => stride * size,
+            _ => Stride::Positive(1),
+        };
+
+        let mut shape = self.clone();
+        shape.sizes.insert(dimension, 1);
+        shape.strides.insert(dimension, stride);
+
+        Ok(shape)
+    }
+
     pub(crate) fn unsqueeze(&self, unsqueezed: usize) -> Result<Shape, UnsqueezeError> {
         let current = self.ndims();
 
@@ -196,10 +260,21 @@ impl Shape {
             }),
             Ordering::Greater => {
                 let ones_len = unsqueezed - current;
-                let mut sizes = self.sizes.to_vec();
-                sizes.splice(..0, repeat(1).take(ones_len));
+                let leading = match (self.strides.first(), self.sizes.first()) {
+                    (Some(&stride), Some(&size)) => stride * size,
+                    _ => Stride::Positive(1),
+                };
 
-                Ok(Shape::new(&sizes))
+                let mut sizes = self.sizes.to_vec();
+                let mut strides = self.strides.to_vec();
+                sizes.splice(..0, repeat_n(1, ones_len));
+                strides.splice(..0, repeat_n(leading, ones_len));
+
+                Ok(Shape {
+                    sizes,
+                    strides,
+                    offset: self.offset,
+                })
             }
         }
     }
@@ -217,6 +292,13 @@ impl Shape {
     }
 
     pub(crate) fn index(&self, indices: &[usize]) -> Result<usize, IndexError> {
+        if indices.len() > self.ndims() {
+            return Err(IndexError::IndicesLength {
+                num_indices: indices.len(),
+                num_dimensions: self.ndims(),
+            });
+        }
+
         let mut indices = indices.to_vec();
         indices.resize(self.ndims(), 0);
         self.valid_indices(&indices, &Vec::from_iter(0..indices.len()))?;
@@ -224,11 +306,15 @@ impl Shape {
         Ok(self.idx(&indices))
     }
 
-    pub(crate) fn index_dims(
-        &self,
-        dimensions: &[usize],
-        indices: &[usize],
-    ) -> Result<usize, IndexError> {
+    pub(crate) fn index_dims(&self, dimensions: &[usize], indices: &[usize]) -> Res<usize> {
+        self.valid_dimensions(dimensions)?;
+        if indices.len() != dimensions.len() {
+            return Err(IndexError::IndicesLength {
+                num_indices: indices.len(),
+                num_dimensions: dimensions.len(),
+            }
+            .into());
+        }
         self.valid_indices(indices, dimensions)?;
 
         Ok((0..self.ndims())
@@ -250,6 +336,78 @@ impl Shape {
             + self.offset)
     }
 
+    pub(crate) fn single_slice(&self, dimension: usize, index: usize) -> Res<Shape> {
+        self.valid_dimensions(&[dimension])?;
+        self.valid_indices(&[index], &[dimension])?;
+
+        let size = self.sizes[dimension];
+        let stride = self.strides[dimension];
+
+        let mut sizes = self.sizes.to_vec();
+        sizes[dimension] = 1;
+
+        Ok(Shape {
+            sizes,
+            strides: self.strides.to_vec(),
+            offset: self.offset + stride.offset(index, size),
+        })
+    }
+
+    pub(crate) fn narrow(&self, dimension: usize, start: usize, length: usize) -> Res<Shape> {
+        self.valid_dimensions(&[dimension])?;
+
+        let size = self.sizes[dimension];
+        let end = start + length;
+        self.valid_ranges(&[(start, end)], &[dimension])?;
+
+        let offset = match self.strides[dimension] {
+            Stride::Positive(stride_val) => self.offset + start * stride_val,
+            Stride::Negative(stride_val) => self.offset + (size - end) * stride_val,
+        };
+
+        let mut sizes = self.sizes.to_vec();
+        sizes[dimension] = length;
+
+        Ok(Shape {
+            sizes,
+            strides: self.strides.to_vec(),
+            offset,
+        })
+    }
+
+    pub(crate) fn slice_step(&self, ranges: &[(usize, usize, isize)]) -> Res<Shape> {
+        self.valid_dimensions(&Vec::from_iter(0..ranges.len()))?;
+
+        let mut shape = self.clone();
+        for (dimension, &(start, end, step)) in ranges.iter().enumerate() {
+            if step == 0 {
+                return Err(StepError.into());
+            }
+
+            let end = if end == 0 { self.sizes[dimension] } else { end };
+            shape.valid_ranges(&[(start, end)], &[dimension])?;
+            shape = shape.narrow(dimension, start, end - start)?;
+
+            let (length, step_val) = (end - start, step.unsigned_abs());
+            let size = length.div_ceil(step_val);
+            let (stride_val, negative) = match shape.strides[dimension] {
+                Stride::Positive(stride_val) => (stride_val, step < 0),
+                Stride::Negative(stride_val) => (stride_val, step > 0),
+            };
+
+            // A negative result stride counts from the last element taken, which sits
+            // `length - 1 - (size - 1) * step` elements before the end of the window.
+            if negative && size > 0 {
+                shape.offset += (length - 1 - (size - 1) * step_val) * stride_val;
+            }
+
+            shape.sizes[dimension] = size;
+            shape.strides[dimension] = Stride::new(stride_val * step_val, !negative);
+        }
+
+        Ok(shape)
+    }
+
     pub(crate) fn slice(&self, indices: &[(usize, usize)]) -> Res<Shape> {
         self.valid_contiguity()?;
 
@@ -368,12 +526,7 @@ impl Shape {
     }
 
     pub(crate) fn slicer(&self, indices: &[Option<usize>]) -> Res<Shape> {
-        self.valid_contiguity()?;
-
-        let mut offset = match self.strides.first().ok_or(EmptyTensorError::Slice)? {
-            Stride::Positive(_) => self.offset,
-            Stride::Negative(_) => self.numel() - 1 - self.offset,
-        };
+        let mut offset = self.offset;
 
         let sizes = self
             .sizes
@@ -382,11 +535,7 @@ impl Shape {
             .zip(indices)
             .map(|((&size, &stride), i)| {
                 if let Some(i) = i {
-                    match stride {
-                        Stride::Positive(stride_val) => offset += i * stride_val,
-                        Stride::Negative(stride_val) => offset -= i * stride_val,
-                    }
-
+                    offset += stride.offset(*i, size);
                     1
                 } else {
                     size
@@ -401,6 +550,42 @@ impl Shape {
         })
     }
 
+    pub(crate) fn unfold(&self, dimension: usize, window: usize, step: usize) -> Res<Shape> {
+        self.valid_dimensions(&[dimension])?;
+
+        let size = self.sizes[dimension];
+        if window == 0 || window > size {
+            return Err(UnfoldError::Window { window, size }.into());
+        } else if step == 0 {
+            return Err(UnfoldError::Step.into());
+        }
+
+        let stride = self.strides[dimension];
+        let mut shape = self.clone();
+
+        shape.sizes[dimension] = (size - window) / step + 1;
+        shape.strides[dimension] = stride * step;
+        shape.sizes.push(window);
+        shape.strides.push(stride);
+
+        Ok(shape)
+    }
+
+    pub(crate) fn all_dimensions(&self) -> Vec<usize> {
+        (0..self.ndims()).collect()
+    }
+
+    pub(crate) fn reduced_sizes(&self, dimensions: &[usize], keepdims: bool) -> Vec<usize> {
+        self.sizes
+            .iter()
+            .enumerate()
+            .filter_map(|(d, &size)| match dimensions.contains(&d) {
+                true => keepdims.then_some(1),
+                false => Some(size),
+            })
+            .collect()
+    }
+
     // --- Broadcast ---
 
     pub(crate) fn broadcast(
@@ -442,7 +627,12 @@ impl Shape {
     // --- Validation ---
 
     pub(crate) fn is_contiguous(&self) -> bool {
-        for i in 0..self.ndims() - 1 {
+        match self.strides.last() {
+            Some(Stride::Positive(1)) | Some(Stride::Negative(1)) | None => {}
+            Some(_) => return false,
+        }
+
+        for i in 0..self.ndims().saturating_sub(1) {
             if self.strides[i] != self.strides[i + 1] * self.sizes[i + 1] {
                 return false;
             }
@@ -451,6 +641,32 @@ impl Shape {
         true
     }
 
+    /// Whether several indices may map to the same element, as in expanded or unfolded views.
+    /// Each stride has to step past everything reachable with the smaller strides, so some
+    /// unusual layouts that don't actually overlap are reported too.
+    pub(crate) fn is_overlapping(&self) -> bool {
+        let mut dimensions = self
+            .sizes
+            .iter()
+            .zip(&self.strides)
+            .filter(|(&size, _)| size > 1)
+            .map(|(&size, stride)| match stride {
+                Stride::Positive(stride_val) | Stride::Negative(stride_val) => (*stride_val, size),
+            })
+            .collect::<Vec<(usize, usize)>>();
+        dimensions.sort_unstable();
+
+        let mut reach = 0;
+        for (stride_val, size) in dimensions {
+            if stride_val <= reach {
+                return true;
+            }
+            reach += stride_val * (size - 1);
+        }
+
+        false
+    }
+
     pub(crate) fn valid_contiguity(&self) -> Result<(), NonContiguousError> {
         if self.is_contiguous() {
             Ok(())
@@ -509,11 +725,11 @@ impl Shape {
     }
 
     pub(crate) fn valid_dimensions(&self, dimensions: &[usize]) -> Result<(), DimensionError> {
-        let dim_range = self.ndims() - 1;
+        let dim_range = self.ndims().saturating_sub(1);
         let mut set = HashSet::with_capacity(dimensions.len());
 
         for &dimension in dimensions {
-            if dim_range < dimension {
+            if dimension >= self.ndims() {
                 return Err(DimensionError::OutOfRange {
                     dimension,
                     dim_range,
@@ -528,7 +744,25 @@ impl Shape {
         Ok(())
     }
 
-    fn valid_ndims(&self, num_indices: usize) -> Result<(), IndexError> {
+    /// Maps a signed axis to a dimension, counting negative axes back from `ndims()`.
+    pub(crate) fn normalize_axis(&self, axis: isize) -> Result<usize, DimensionError> {
+        let ndims = self.ndims();
+        let dimension = if axis < 0 {
+            ndims.checked_sub(axis.unsigned_abs())
+        } else {
+            Some(axis as usize)
+        };
+
+        dimension
+            .filter(|&dimension| dimension < ndims)
+            .ok_or(DimensionError::AxisOutOfRange { axis, ndims })
+    }
+
+    pub(crate) fn normalize_axes(&self, axes: &[isize]) -> Result<Vec<usize>, DimensionError> {
+        axes.iter().map(|&axis| self.normalize_axis(axis)).collect()
+    }
+
+    pub(crate) fn valid_ndims(&self, num_indices: usize) -> Result<(), IndexError> {
         let num_dimensions = self.ndims();
 
         if num_indices != num_dimensions {
@@ -541,6 +775,26 @@ impl Shape {
         }
     }
 
+    pub(crate) fn valid_ndims_exact(&self, expected: usize) -> Result<(), NdimsError> {
+        let ndims = self.ndims();
+
+        if ndims != expected {
+            Err(NdimsError { expected, ndims })
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn valid_min_ndims(&self, minimum: usize) -> Result<(), MinNdimsError> {
+        let ndims = self.ndims();
+
+        if ndims < minimum {
+            Err(MinNdimsError { minimum, ndims })
+        } else {
+            Ok(())
+        }
+    }
+
     pub(crate) fn valid_data_length(
         &self,
         data_length: usize,
@@ -557,6 +811,48 @@ impl Shape {
         }
     }
 
+    pub(crate) fn valid_index_sizes(
+        &self,
+        indices_sizes: &[usize],
+        dimension: usize,
+    ) -> Result<(), IndexTensorError> {
+        let (indices_ndims, tensor_ndims) = (indices_sizes.len(), self.ndims());
+
+        if indices_ndims != tensor_ndims {
+            return Err(IndexTensorError::Ndims {
+                indices_ndims,
+                tensor_ndims,
+            });
+        }
+
+        for (d, (&indices_size, &tensor_size)) in indices_sizes.iter().zip(&self.sizes).enumerate()
+        {
+            if d != dimension && indices_size != tensor_size {
+                return Err(IndexTensorError::Size {
+                    dimension: d,
+                    indices_size,
+                    tensor_size,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn valid_matching(
+        lhs_sizes: &[usize],
+        rhs_sizes: &[usize],
+    ) -> Result<(), ShapeMismatchError> {
+        if lhs_sizes != rhs_sizes {
+            Err(ShapeMismatchError {
+                lhs_sizes: lhs_sizes.to_vec(),
+                rhs_sizes: rhs_sizes.to_vec(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
     pub(crate) fn conv_larger_input(
         input_sizes: &[usize],
         kernel_sizes: &[usize],
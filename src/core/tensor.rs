@@ -4,18 +4,26 @@ use crate::{
         errors::*,
         iters::{Indexer, Slicer},
         shape::{Shape, Stride},
-        utils::cast_usize,
+        utils::{cast_usize, MaybeSendSync},
     },
 };
 use num_traits::{FromPrimitive, NumOps, One, Zero};
 use std::{fmt::Debug, iter::successors, ops::Add, sync::Arc};
 
+/// An N-dimensional view over shared data. Views share the data through an `Arc`, so a tensor
+/// is `Send` and `Sync` whenever `T` is, and views can be moved into other threads cheaply.
 pub struct Tensor<T> {
     pub(crate) data: Arc<Vec<T>>,
     pub(crate) shape: Shape,
 }
 
-impl<T: Copy> Tensor<T> {
+pub enum PadAlign {
+    Before,
+    After,
+    Center,
+}
+
+impl<T: Copy + MaybeSendSync> Tensor<T> {
     // --- Init ---
 
     pub(crate) fn init(data: Vec<T>, sizes: &[usize]) -> Tensor<T> {
@@ -118,6 +126,53 @@ impl<T: Copy> Tensor<T> {
         Ok(Tensor::init(data, &[size, size]))
     }
 
+    pub fn diagflat(values: &Tensor<T>) -> Res<Tensor<T>>
+    where
+        T: Zero,
+    {
+        let values = values.data();
+        let size = values.len();
+
+        let mut data = vec![T::zero(); size * size];
+        for (i, value) in values.into_iter().enumerate() {
+            data[i * (size + 1)] = value;
+        }
+
+        Ok(Tensor::init(data, &[size, size]))
+    }
+
+    pub fn block_diag(tensors: &[&Tensor<T>]) -> Res<Tensor<T>>
+    where
+        T: Zero,
+    {
+        for tensor in tensors {
+            tensor.shape.valid_ndims_exact(2)?;
+        }
+
+        let rows = tensors
+            .iter()
+            .map(|tensor| tensor.sizes()[0])
+            .sum::<usize>();
+        let columns = tensors
+            .iter()
+            .map(|tensor| tensor.sizes()[1])
+            .sum::<usize>();
+        let mut data = vec![T::zero(); rows * columns];
+
+        let (mut row_offset, mut column_offset) = (0, 0);
+        for tensor in tensors {
+            for index in Indexer::new(tensor.sizes()) {
+                let offset = (row_offset + index[0]) * columns + column_offset + index[1];
+                data[offset] = tensor.idx(&index);
+            }
+
+            row_offset += tensor.sizes()[0];
+            column_offset += tensor.sizes()[1];
+        }
+
+        Ok(Tensor::init(data, &[rows, columns]))
+    }
+
     pub fn to_contiguous(&self) -> Result<Tensor<T>, PhantomError> {
         Ok(Tensor {
             data: Arc::new(self.data_non_contiguous()),
@@ -133,22 +188,107 @@ impl<T: Copy> Tensor<T> {
         }
     }
 
+    /// Returns `self` unchanged, sharing its data, if its elements already lie in row-major
+    /// order in memory. Otherwise, including for flipped tensors, copies them into a new
+    /// contiguous tensor.
+    pub fn ensure_contiguous(self) -> Res<Tensor<T>> {
+        match self.strides().first() {
+            Some(Stride::Negative(_)) => Ok(self.to_contiguous()?),
+            _ => Ok(self.into_contiguous()?),
+        }
+    }
+
+    /// Consuming `ravel` that always returns a 1d contiguous tensor. When the tensor is the only
+    /// owner of its buffer, the buffer is trimmed (and un-flipped) in place instead of copied.
+    pub fn ravel_into(self) -> Res<Tensor<T>> {
+        if !self.is_contiguous() {
+            return self.to_contiguous()?.ravel();
+        }
+
+        let (start, numel) = (self.offset(), self.numel());
+        let flipped = matches!(self.strides().first(), Some(Stride::Negative(_)));
+
+        match Arc::try_unwrap(self.data) {
+            Ok(mut data) => {
+                data.truncate(start + numel);
+                data.drain(..start);
+                if flipped {
+                    data.reverse();
+                }
+
+                Ok(Tensor::init(data, &[numel]))
+            }
+            Err(data) => Tensor {
+                data,
+                shape: self.shape,
+            }
+            .ensure_contiguous()?
+            .ravel(),
+        }
+    }
+
     // --- Data ---
 
     pub fn data(&self) -> Vec<T> {
         if self.is_contiguous() {
-            self.data_contiguous().to_vec()
+            match self.shape.strides.first() {
+                Some(Stride::Negative(_)) => self.data_contiguous().iter().rev().copied().collect(),
+                _ => self.data_contiguous().to_vec(),
+            }
         } else {
             self.data_non_contiguous()
         }
     }
 
+    pub fn to_vec2(&self) -> Res<Vec<Vec<T>>> {
+        self.shape.valid_ndims_exact(2)?;
+        Ok(nest(&self.data(), self.sizes()[0]))
+    }
+
+    pub fn to_vec3(&self) -> Res<Vec<Vec<Vec<T>>>> {
+        self.shape.valid_ndims_exact(3)?;
+        let sizes = self.sizes();
+
+        Ok(nest(&self.data(), sizes[0])
+            .into_iter()
+            .map(|matrix| nest(&matrix, sizes[1]))
+            .collect())
+    }
+
+    pub fn to_vec4(&self) -> Res<Vec<Vec<Vec<Vec<T>>>>> {
+        self.shape.valid_ndims_exact(4)?;
+        let sizes = self.sizes();
+
+        Ok(nest(&self.data(), sizes[0])
+            .into_iter()
+            .map(|cube| {
+                nest(&cube, sizes[1])
+                    .into_iter()
+                    .map(|matrix| nest(&matrix, sizes[2]))
+                    .collect()
+            })
+            .collect())
+    }
+
     pub(crate) fn data_contiguous(&self) -> &[T] {
         let start = self.offset();
         let end = start + self.numel();
         &self.data[start..end]
     }
 
+    /// Mutable access to the elements of a contiguous tensor in row-major order. Data shared
+    /// with other tensors is copied first, so they are unaffected.
+    pub fn as_mut_slice(&mut self) -> Res<&mut [T]> {
+        if !self.is_contiguous() || matches!(self.strides().first(), Some(Stride::Negative(_))) {
+            return Err(NonContiguousError.into());
+        }
+
+        let start = self.offset();
+        let end = start + self.numel();
+
+        Ok(&mut Arc::make_mut(&mut self.data)[start..end])
+    }
+
     pub(crate) fn data_non_contiguous(&self) -> Vec<T> {
         Indexer::new(&self.shape.sizes)
             .map(|index| self.idx(&index))
@@ -163,15 +303,20 @@ impl<T: Copy> Tensor<T> {
         Ok(self.data[self.shape.index(indices)?])
     }
 
-    pub fn index_dims(&self, dimensions: &[usize], indices: &[usize]) -> Result<T, IndexError> {
+    pub fn index_dims(&self, dimensions: &[usize], indices: &[usize]) -> Res<T> {
         Ok(self.data[self.shape.index_dims(dimensions, indices)?])
     }
 
     // --- New Data, New Shape ---
 
+    /// Shares the data like `view` when the tensor is contiguous, and copies it otherwise.
     pub fn reshape(&self, sizes: &[usize]) -> Res<Tensor<T>> {
         self.shape.valid_reshape(sizes)?;
 
+        if self.is_contiguous() && self.ndims() > 0 {
+            return self.view(sizes);
+        }
+
         Ok(Tensor::init(self.data_non_contiguous(), sizes))
     }
 
@@ -183,6 +328,53 @@ impl<T: Copy> Tensor<T> {
         self.view(sizes).or_else(|_| self.reshape(sizes))
     }
 
+    pub fn reshape_as(&self, other: &Tensor<T>) -> Res<Tensor<T>> {
+        self.view_else_reshape(other.sizes())
+    }
+
+    /// Splits `[B, L, D]` into `[B, num_heads, L, D / num_heads]`.
+    pub fn split_heads(&self, num_heads: usize) -> Res<Tensor<T>> {
+        self.shape.valid_ndims_exact(3)?;
+
+        let &[batch, length, size] = self.sizes() else {
+            unreachable!()
+        };
+        if num_heads == 0 || size % num_heads != 0 {
+            return Err(HeadsError {
+                size,
+                heads: num_heads,
+            }
+            .into());
+        }
+
+        self.view_else_reshape(&[batch, length, num_heads, size / num_heads])?
+            .transpose(1, 2)
+    }
+
+    /// Merges `[B, num_heads, L, D / num_heads]` back into `[B, L, D]`.
+    pub fn merge_heads(&self) -> Res<Tensor<T>> {
+        self.shape.valid_ndims_exact(4)?;
+
+        let &[batch, num_heads, length, head_size] = self.sizes() else {
+            unreachable!()
+        };
+
+        self.transpose(1, 2)?
+            .view_else_reshape(&[batch, length, num_heads * head_size])
+    }
+
+    pub fn unfold(&self, dimension: usize, window: usize, step: usize) -> Res<Tensor<T>> {
+        let tensor = match self.shape.strides.get(dimension) {
+            Some(Stride::Negative(_)) => self.to_contiguous()?,
+            _ => self.view_same(),
+        };
+
+        Ok(Tensor {
+            shape: tensor.shape.unfold(dimension, window, step)?,
+            data: tensor.data,
+        })
+    }
+
     pub fn pad(&self, constant: T, padding: &[(usize, usize)]) -> Res<Tensor<T>> {
         let shape = self.shape.pad(padding)?;
         let data = Arc::new(vec![constant; shape.numel()]);
@@ -197,6 +389,13 @@ impl<T: Copy> Tensor<T> {
         tensor.slice_zip(&self.data(), |_, new| new, &ranges)
     }
 
+    pub fn pad_zeroes(&self, padding: &[(usize, usize)]) -> Res<Tensor<T>>
+    where
+        T: Zero,
+    {
+        self.pad(T::zero(), padding)
+    }
+
     pub fn pad_dims(
         &self,
         constant: T,
@@ -216,9 +415,266 @@ impl<T: Copy> Tensor<T> {
         tensor.slice_zip_dims(&self.data(), |_, new| new, dimensions, &ranges)
     }
 
+    /// Like [`Tensor::pad_dims`], but a negative amount crops that many elements from its side.
+    pub fn pad_crop(
+        &self,
+        dimensions: &[usize],
+        padding: &[(isize, isize)],
+        value: T,
+    ) -> Res<Tensor<T>> {
+        self.shape.valid_dimensions(dimensions)?;
+        if dimensions.len() != padding.len() {
+            return Err(CropError::Length {
+                dimensions: dimensions.len(),
+                padding: padding.len(),
+            }
+            .into());
+        }
+
+        let mut cropped = self.view_same();
+        for (&dimension, &(start, end)) in dimensions.iter().zip(padding) {
+            let size = self.sizes()[dimension];
+            let (start_crop, end_crop) = (start.min(0).unsigned_abs(), end.min(0).unsigned_abs());
+            let crop = start_crop + end_crop;
+
+            if crop > size {
+                return Err(CropError::Size {
+                    dimension,
+                    size,
+                    crop,
+                }
+                .into());
+            }
+
+            cropped = cropped.narrow(dimension, start_crop, size - crop)?;
+        }
+
+        let padding = padding
+            .iter()
+            .map(|&(start, end)| (start.max(0).unsigned_abs(), end.max(0).unsigned_abs()))
+            .collect::<Vec<(usize, usize)>>();
+
+        cropped.pad_dims(value, dimensions, &padding)
+    }
+
+    pub fn tile_to(&self, sizes: &[usize]) -> Res<Tensor<T>> {
+        let tensor = self.unsqueeze(sizes.len())?;
+
+        for (&size, &target) in tensor.sizes().iter().zip(sizes) {
+            if size == 0 || target % size != 0 {
+                return Err(TileError { size, target }.into());
+            }
+        }
+
+        Ok(tensor.tile_unchecked(sizes))
+    }
+
+    /// Tiles the tensor `repeats[i]` times along each dimension, like NumPy's `tile`. Leading
+    /// dimensions are prepended when `repeats` is longer than `ndims()`. Unlike `expand`, the
+    /// data is duplicated, and the result is always contiguous even when `self` is a strided or
+    /// flipped view, so it can be passed straight to `view`.
+    pub fn repeat(&self, repeats: &[usize]) -> Res<Tensor<T>> {
+        let tensor = self.unsqueeze(repeats.len())?;
+        let sizes = tensor
+            .sizes()
+            .iter()
+            .zip(repeats)
+            .map(|(&size, &repeat)| size * repeat)
+            .collect::<Vec<usize>>();
+
+        Ok(tensor.tile_unchecked(&sizes))
+    }
+
+    // Fills the whole output in one allocation, indexing the source modulo its sizes.
+    fn tile_unchecked(&self, sizes: &[usize]) -> Tensor<T> {
+        let mut data = Vec::with_capacity(sizes.iter().product());
+        data.extend(Indexer::new(sizes).map(|index| {
+            let source = index
+                .iter()
+                .zip(self.sizes())
+                .map(|(&i, &size)| i % size)
+                .collect::<Vec<usize>>();
+
+            self.idx(&source)
+        }));
+
+        Tensor::init(data, sizes)
+    }
+
+    /// Joins tensors along an existing `dimension` into a new contiguous tensor.
+    pub fn concat(tensors: &[&Tensor<T>], dimension: usize) -> Res<Tensor<T>> {
+        let first = tensors.first().ok_or(ConcatError::Empty)?;
+        first.shape.valid_dimensions(&[dimension])?;
+
+        let expected = first.ndims();
+        for tensor in tensors {
+            if tensor.ndims() != expected {
+                let ndims = tensor.ndims();
+                return Err(ConcatError::Ndims { expected, ndims }.into());
+            }
+
+            for (dim, (&expected, &size)) in first.sizes().iter().zip(tensor.sizes()).enumerate() {
+                if dim != dimension && size != expected {
+                    return Err(ConcatError::Size {
+                        concat_dimension: dimension,
+                        dimension: dim,
+                        expected,
+                        size,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        let ends = tensors
+            .iter()
+            .scan(0, |end, tensor| {
+                *end += tensor.sizes()[dimension];
+                Some(*end)
+            })
+            .collect::<Vec<usize>>();
+
+        let mut sizes = first.sizes().to_vec();
+        sizes[dimension] = ends[ends.len() - 1];
+
+        let mut data = Vec::with_capacity(sizes.iter().product());
+        data.extend(Indexer::new(&sizes).map(|mut index| {
+            let source = ends.partition_point(|&end| end <= index[dimension]);
+            index[dimension] -= ends[source] - tensors[source].sizes()[dimension];
+
+            tensors[source].idx(&index)
+        }));
+
+        Ok(Tensor::init(data, &sizes))
+    }
+
+    pub fn stack(tensors: &[&Tensor<T>], dimension: usize) -> Res<Tensor<T>> {
+        let first = tensors.first().ok_or(ConcatError::Empty)?;
+
+        let unsqueezed = tensors
+            .iter()
+            .map(|tensor| {
+                Shape::valid_matching(first.sizes(), tensor.sizes())?;
+                Ok(tensor.unsqueeze_dim(dimension)?)
+            })
+            .collect::<Res<Vec<Tensor<T>>>>()?;
+
+        Tensor::concat(&unsqueezed.iter().collect::<Vec<_>>(), dimension)
+    }
+
+    /// Rotates elements `amount` places along `dimension`, wrapping around the ends. Like the
+    /// other copying ops, the result is always a new contiguous tensor.
+    pub fn roll(&self, amount: isize, dimension: usize) -> Res<Tensor<T>> {
+        self.shape.valid_dimensions(&[dimension])?;
+
+        let size = self.sizes()[dimension] as isize;
+        let data = Indexer::new(self.sizes())
+            .map(|mut index| {
+                index[dimension] = (index[dimension] as isize - amount).rem_euclid(size) as usize;
+                self.idx(&index)
+            })
+            .collect();
+
+        Ok(Tensor::init(data, self.sizes()))
+    }
+
+    /// Moves elements `amount` places along `dimension`, filling vacated positions with `fill`.
+    /// The result is contiguous, so it can be passed straight to `view`.
+    pub fn shift(&self, amount: isize, dimension: usize, fill: T) -> Res<Tensor<T>> {
+        self.shape.valid_dimensions(&[dimension])?;
+
+        let size = self.sizes()[dimension] as isize;
+        let data = Indexer::new(self.sizes())
+            .map(|mut index| {
+                let source = index[dimension] as isize - amount;
+
+                if (0..size).contains(&source) {
+                    index[dimension] = source as usize;
+                    self.idx(&index)
+                } else {
+                    fill
+                }
+            })
+            .collect();
+
+        Ok(Tensor::init(data, self.sizes()))
+    }
+
+    pub fn causal_shift(&self, dimension: usize, fill: T) -> Res<Tensor<T>> {
+        self.shift(1, dimension, fill)
+    }
+
+    pub fn pad_to(&self, sizes: &[usize], constant: T, align: PadAlign) -> Res<Tensor<T>> {
+        self.shape.valid_ndims(sizes.len())?;
+
+        let padding = self
+            .sizes()
+            .iter()
+            .zip(sizes)
+            .map(|(&size, &target)| {
+                let difference = target
+                    .checked_sub(size)
+                    .ok_or(PadToError { size, target })?;
+
+                Ok(match align {
+                    PadAlign::Before => (difference, 0),
+                    PadAlign::After => (0, difference),
+                    PadAlign::Center => (difference / 2, difference - difference / 2),
+                })
+            })
+            .collect::<Result<Vec<(usize, usize)>, PadToError>>()?;
+
+        self.pad(constant, &padding)
+    }
+
+    pub fn trim(&self, value: T) -> Res<Tensor<T>>
+    where
+        T: PartialEq,
+    {
+        let mut bounds = vec![None::<(usize, usize)>; self.ndims()];
+
+        for index in Indexer::new(self.sizes()).filter(|index| self.idx(index) != value) {
+            for (bound, &i) in bounds.iter_mut().zip(&index) {
+                *bound = match *bound {
+                    Some((start, end)) => Some((start.min(i), end.max(i + 1))),
+                    None => Some((i, i + 1)),
+                };
+            }
+        }
+
+        if bounds.iter().any(Option::is_none) {
+            return Ok(Tensor::init(Vec::new(), &vec![0; self.ndims()]));
+        }
+
+        bounds
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .try_fold(self.view_same(), |tensor, (dimension, (start, end))| {
+                tensor.narrow(dimension, start, end - start)
+            })
+    }
+
+    /// Compares sizes and logical values, returning false on a shape mismatch.
+    pub fn equal(&self, other: &Tensor<T>) -> bool
+    where
+        T: PartialEq,
+    {
+        self.sizes() == other.sizes()
+            && Indexer::new(self.sizes()).all(|index| self.idx(&index) == other.idx(&index))
+    }
+
     // --- Maps, Zips and Reduce ---
 
-    pub fn unary_map<R>(&self, f: impl Fn(T) -> R) -> Res<Tensor<R>> {
+    pub fn unary_map<R>(&self, f: impl Fn(T) -> R + MaybeSendSync) -> Res<Tensor<R>>
+    where
+        R: MaybeSendSync,
+    {
+        #[cfg(feature = "rayon")]
+        if self.parallelizable() {
+            return Ok(self.par_unary_map(f));
+        }
+
         let (data, shape) = if self.is_contiguous() {
             (
                 self.data_contiguous().iter().map(|&elem| f(elem)).collect(),
@@ -246,7 +702,56 @@ impl<T: Copy> Tensor<T> {
         })
     }
 
-    pub fn binary_map<R>(&self, rhs: T, f: impl Fn(T, T) -> R) -> Res<Tensor<R>> {
+    /// Converts every element with `Into`, so only lossless conversions are available. For
+    /// narrowing conversions, map with `as` through `unary_map`, accepting the precision loss.
+    pub fn cast<R>(&self) -> Res<Tensor<R>>
+    where
+        T: Into<R>,
+        R: MaybeSendSync,
+    {
+        self.unary_map(Into::into)
+    }
+
+    pub fn to_f32(&self) -> Res<Tensor<f32>>
+    where
+        T: Into<f32>,
+    {
+        self.cast()
+    }
+
+    pub fn to_f64(&self) -> Res<Tensor<f64>>
+    where
+        T: Into<f64>,
+    {
+        self.cast()
+    }
+
+    pub fn to_i64(&self) -> Res<Tensor<i64>>
+    where
+        T: Into<i64>,
+    {
+        self.cast()
+    }
+
+    pub fn flat_map<R, const N: usize>(&self, f: impl Fn(T) -> [R; N]) -> Res<Tensor<R>> {
+        let data = self.data().into_iter().flat_map(f).collect();
+        let sizes = [self.sizes(), &[N]].concat();
+
+        Ok(Tensor {
+            data: Arc::new(data),
+            shape: Shape::new(&sizes),
+        })
+    }
+
+    pub fn binary_map<R>(&self, rhs: T, f: impl Fn(T, T) -> R + MaybeSendSync) -> Res<Tensor<R>>
+    where
+        R: MaybeSendSync,
+    {
+        #[cfg(feature = "rayon")]
+        if self.parallelizable() {
+            return Ok(self.par_binary_map(rhs, f));
+        }
+
         let (data, shape) = if self.is_contiguous() {
             (
                 self.data_contiguous()
@@ -277,7 +782,22 @@ impl<T: Copy> Tensor<T> {
         })
     }
 
-    pub fn zip<R>(&self, rhs: &Tensor<T>, f: impl Fn(T, T) -> R) -> Res<Tensor<R>> {
+    pub fn zip<R>(&self, rhs: &Tensor<T>, f: impl Fn(T, T) -> R + MaybeSendSync) -> Res<Tensor<R>>
+    where
+        R: MaybeSendSync,
+    {
+        self.zip_with(rhs, f)
+    }
+
+    pub fn zip_with<U, R>(
+        &self,
+        rhs: &Tensor<U>,
+        f: impl Fn(T, U) -> R + MaybeSendSync,
+    ) -> Res<Tensor<R>>
+    where
+        U: Copy + MaybeSendSync,
+        R: MaybeSendSync,
+    {
         if self.shape == rhs.shape {
             self.equal_zip(rhs, f)
         } else {
@@ -285,7 +805,20 @@ impl<T: Copy> Tensor<T> {
         }
     }
 
-    fn equal_zip<R>(&self, rhs: &Tensor<T>, f: impl Fn(T, T) -> R) -> Res<Tensor<R>> {
+    fn equal_zip<U, R>(
+        &self,
+        rhs: &Tensor<U>,
+        f: impl Fn(T, U) -> R + MaybeSendSync,
+    ) -> Res<Tensor<R>>
+    where
+        U: Copy + MaybeSendSync,
+        R: MaybeSendSync,
+    {
+        #[cfg(feature = "rayon")]
+        if self.parallelizable() && rhs.is_contiguous() {
+            return Ok(self.par_equal_zip(rhs, f));
+        }
+
         let (data, shape) = if self.is_contiguous() && rhs.is_contiguous() {
             (
                 self.data_contiguous()
@@ -319,7 +852,15 @@ impl<T: Copy> Tensor<T> {
         })
     }
 
-    fn broadcast_zip<R>(&self, rhs: &Tensor<T>, f: impl Fn(T, T) -> R) -> Res<Tensor<R>> {
+    fn broadcast_zip<U, R>(
+        &self,
+        rhs: &Tensor<U>,
+        f: impl Fn(T, U) -> R + MaybeSendSync,
+    ) -> Res<Tensor<R>>
+    where
+        U: Copy + MaybeSendSync,
+        R: MaybeSendSync,
+    {
         let sizes = Shape::broadcast(&self.shape.sizes, &rhs.shape.sizes)?;
         let shape = Shape::new(&sizes);
         let expansion = sizes.len();
@@ -341,6 +882,51 @@ impl<T: Copy> Tensor<T> {
         Ok(Tensor { data, shape })
     }
 
+    pub fn zip3<R>(
+        &self,
+        mid: &Tensor<T>,
+        rhs: &Tensor<T>,
+        f: impl Fn(T, T, T) -> R,
+    ) -> Res<Tensor<R>> {
+        let sizes = Shape::broadcast(&self.shape.sizes, &mid.shape.sizes)?;
+        let sizes = Shape::broadcast(&sizes, &rhs.shape.sizes)?;
+        let shape = Shape::new(&sizes);
+        let expansion = sizes.len();
+
+        let lhs_broadcasted = self.unsqueeze(expansion)?.expand(&sizes)?;
+        let mid_broadcasted = mid.unsqueeze(expansion)?.expand(&sizes)?;
+        let rhs_broadcasted = rhs.unsqueeze(expansion)?.expand(&sizes)?;
+
+        let data = Arc::new(
+            Indexer::new(&shape.sizes)
+                .map(|index| {
+                    let lhs_elem = lhs_broadcasted.idx(&index);
+                    let mid_elem = mid_broadcasted.idx(&index);
+                    let rhs_elem = rhs_broadcasted.idx(&index);
+
+                    f(lhs_elem, mid_elem, rhs_elem)
+                })
+                .collect(),
+        );
+
+        Ok(Tensor { data, shape })
+    }
+
+    pub fn outer_map<U, R>(
+        &self,
+        rhs: &Tensor<U>,
+        f: impl Fn(T, U) -> R + MaybeSendSync,
+    ) -> Res<Tensor<R>>
+    where
+        U: Copy + MaybeSendSync,
+        R: MaybeSendSync,
+    {
+        let lhs =
+            (0..rhs.ndims()).try_fold(self.view_same(), |lhs, _| lhs.unsqueeze_dim(lhs.ndims()))?;
+
+        lhs.zip_with(rhs, f)
+    }
+
     pub fn zip_array<R>(&self, rhs: &[T], f: impl Fn(T, T) -> R) -> Res<Tensor<R>> {
         self.shape.valid_data_length(rhs.len())?;
 
@@ -360,38 +946,72 @@ impl<T: Copy> Tensor<T> {
         })
     }
 
+    /// Applies `f` to the slices spanning `dimensions`, folding each to one value. With `keepdims`
+    /// the reduced dimensions stay as size 1, otherwise they are removed. An empty `dimensions`
+    /// reduces over every dimension.
     pub fn reduce<R>(
         &self,
         dimensions: &[usize],
-        f: impl Fn(&Tensor<T>) -> Res<R>,
+        f: impl Fn(&Tensor<T>) -> Res<R> + MaybeSendSync,
         keepdims: bool,
     ) -> Res<Tensor<R>>
     where
-        R: Copy,
+        R: Copy + MaybeSendSync,
     {
+        if dimensions.is_empty() && self.ndims() > 0 {
+            return self.reduce(&self.shape.all_dimensions(), f, keepdims);
+        }
         self.shape.valid_dimensions(dimensions)?;
 
-        let data = Slicer::new(&self.shape.sizes, dimensions, keepdims)
+        let sizes = self.shape.reduced_sizes(dimensions, keepdims);
+
+        #[cfg(feature = "rayon")]
+        if self.parallelizable() {
+            return Ok(Tensor::init(self.par_reduce_slices(dimensions, f)?, &sizes));
+        }
+
+        let data = Slicer::new(&self.shape.sizes, dimensions, true)
             .map(|index| f(&self.slicer(&index)?))
             .collect::<Res<Vec<R>>>()?;
 
-        let sizes: Vec<usize> = self
-            .shape
-            .sizes
-            .iter()
-            .enumerate()
-            .map(|(d, &size)| {
-                if keepdims == dimensions.contains(&d) {
-                    1
-                } else {
-                    size
-                }
-            })
-            .collect();
-
         Ok(Tensor::init(data, &sizes))
     }
 
+    pub fn reduce_axes<R>(
+        &self,
+        axes: &[isize],
+        f: impl Fn(&Tensor<T>) -> Res<R> + MaybeSendSync,
+        keepdims: bool,
+    ) -> Res<Tensor<R>>
+    where
+        R: Copy + MaybeSendSync,
+    {
+        self.reduce(&self.shape.normalize_axes(axes)?, f, keepdims)
+    }
+
+    pub fn reduce_with_index<R>(
+        &self,
+        dimensions: &[usize],
+        f: impl Fn(&Tensor<T>) -> Res<(R, usize)>,
+        keepdims: bool,
+    ) -> Res<(Tensor<R>, Tensor<usize>)>
+    where
+        R: Copy + MaybeSendSync,
+    {
+        if dimensions.is_empty() && self.ndims() > 0 {
+            return self.reduce_with_index(&self.shape.all_dimensions(), f, keepdims);
+        }
+        self.shape.valid_dimensions(dimensions)?;
+
+        let (data, indices) = Slicer::new(&self.shape.sizes, dimensions, true)
+            .map(|index| f(&self.slicer(&index)?))
+            .collect::<Res<(Vec<R>, Vec<usize>)>>()?;
+
+        let sizes = self.shape.reduced_sizes(dimensions, keepdims);
+
+        Ok((Tensor::init(data, &sizes), Tensor::init(indices, &sizes)))
+    }
+
     pub fn index_map(&self, f: impl Fn(T) -> T, index: &[usize]) -> Res<Tensor<T>> {
         let mut data = self.data();
         let offset = self.shape.index(index)?;
@@ -503,6 +1123,13 @@ impl<T: Copy> Tensor<T> {
 impl<T> Tensor<T> {
     // --- Same Data, Modified Shape ---
 
+    pub(crate) fn view_same(&self) -> Tensor<T> {
+        Tensor {
+            data: Arc::clone(&self.data),
+            shape: self.shape.clone(),
+        }
+    }
+
     pub fn view(&self, sizes: &[usize]) -> Res<Tensor<T>> {
         Ok(Tensor {
             data: Arc::clone(&self.data),
@@ -521,6 +1148,24 @@ impl<T> Tensor<T> {
         })
     }
 
+    pub fn squeeze_all_but(&self, keep: &[usize]) -> Res<Tensor<T>> {
+        Ok(Tensor {
+            data: Arc::clone(&self.data),
+            shape: self.shape.squeeze_all_but(keep)?,
+        })
+    }
+
+    pub fn squeeze_axis(&self, axis: isize) -> Res<Tensor<T>> {
+        self.squeeze_dim(self.shape.normalize_axis(axis)?)
+    }
+
+    pub fn squeeze_dim(&self, dimension: usize) -> Res<Tensor<T>> {
+        Ok(Tensor {
+            data: Arc::clone(&self.data),
+            shape: self.shape.squeeze_dim(dimension)?,
+        })
+    }
+
     pub fn unsqueeze(&self, unsqueezed: usize) -> Result<Tensor<T>, UnsqueezeError> {
         Ok(Tensor {
             data: Arc::clone(&self.data),
@@ -528,6 +1173,29 @@ impl<T> Tensor<T> {
         })
     }
 
+    pub fn unsqueeze_dim(&self, dimension: usize) -> Result<Tensor<T>, DimensionError> {
+        Ok(Tensor {
+            data: Arc::clone(&self.data),
+            shape: self.shape.unsqueeze_dim(dimension)?,
+        })
+    }
+
+    pub fn atleast_1d(&self) -> Res<Tensor<T>> {
+        self.atleast(1)
+    }
+
+    pub fn atleast_2d(&self) -> Res<Tensor<T>> {
+        self.atleast(2)
+    }
+
+    pub fn atleast_3d(&self) -> Res<Tensor<T>> {
+        self.atleast(3)
+    }
+
+    fn atleast(&self, ndims: usize) -> Res<Tensor<T>> {
+        Ok(self.unsqueeze(ndims.max(self.ndims()))?)
+    }
+
     pub fn permute(&self, permutation: &[usize]) -> Res<Tensor<T>> {
         Ok(Tensor {
             data: Arc::clone(&self.data),
@@ -535,6 +1203,19 @@ impl<T> Tensor<T> {
         })
     }
 
+    pub fn permute_axes(&self, axes: &[isize]) -> Res<Tensor<T>> {
+        self.permute(&self.shape.normalize_axes(axes)?)
+    }
+
+    pub fn transpose_axis(&self, axis_1: isize, axis_2: isize) -> Res<Tensor<T>> {
+        let (dim_1, dim_2) = (
+            self.shape.normalize_axis(axis_1)?,
+            self.shape.normalize_axis(axis_2)?,
+        );
+
+        self.transpose(dim_1, dim_2)
+    }
+
     pub fn transpose(&self, dim_1: usize, dim_2: usize) -> Res<Tensor<T>> {
         Ok(Tensor {
             data: Arc::clone(&self.data),
@@ -549,6 +1230,24 @@ impl<T> Tensor<T> {
         })
     }
 
+    pub fn broadcast_to(&self, sizes: &[usize]) -> Res<Tensor<T>> {
+        self.unsqueeze(sizes.len())?.expand(sizes)
+    }
+
+    pub fn expand_as(&self, other: &Tensor<T>) -> Res<Tensor<T>> {
+        self.broadcast_to(other.sizes())
+    }
+
+    /// Zero-copy repeat along a new axis at `dimension`, which has a stride of 0.
+    pub fn broadcast_repeat(&self, dimension: usize, times: usize) -> Res<Tensor<T>> {
+        let unsqueezed = self.unsqueeze_dim(dimension)?;
+
+        let mut sizes = unsqueezed.sizes().to_vec();
+        sizes[dimension] = times;
+
+        unsqueezed.expand(&sizes)
+    }
+
     pub fn flip(&self, flips: &[usize]) -> Result<Tensor<T>, DimensionError> {
         Ok(Tensor {
             data: Arc::clone(&self.data),
@@ -560,6 +1259,28 @@ impl<T> Tensor<T> {
         self.flip(&Vec::from_iter(0..self.ndims()))
     }
 
+    pub fn fliplr(&self) -> Res<Tensor<T>> {
+        self.shape.valid_min_ndims(2)?;
+        Ok(self.flip(&[self.ndims() - 1])?)
+    }
+
+    pub fn flipud(&self) -> Res<Tensor<T>> {
+        self.shape.valid_min_ndims(1)?;
+        Ok(self.flip(&[0])?)
+    }
+
+    pub fn rot90(&self, k: i32, dimensions: (usize, usize)) -> Res<Tensor<T>> {
+        let (dim_1, dim_2) = dimensions;
+        self.shape.valid_dimensions(&[dim_1, dim_2])?;
+
+        match k.rem_euclid(4) {
+            1 => self.flip(&[dim_2])?.transpose(dim_1, dim_2),
+            2 => Ok(self.flip(&[dim_1, dim_2])?),
+            3 => Ok(self.transpose(dim_1, dim_2)?.flip(&[dim_2])?),
+            _ => Ok(self.view_same()),
+        }
+    }
+
     pub fn slice(&self, ranges: &[(usize, usize)]) -> Res<Tensor<T>> {
         Ok(Tensor {
             data: Arc::clone(&self.data),
@@ -574,6 +1295,55 @@ impl<T> Tensor<T> {
         })
     }
 
+    /// Slices `(start, end, step)` per leading dimension as a view. A negative step walks the
+    /// range backwards from `end - 1`, and an `end` of 0 means the full size, as in `slice`.
+    pub fn slice_step(&self, ranges: &[(usize, usize, isize)]) -> Res<Tensor<T>> {
+        Ok(Tensor {
+            data: Arc::clone(&self.data),
+            shape: self.shape.slice_step(ranges)?,
+        })
+    }
+
+    pub fn narrow(&self, dimension: usize, start: usize, length: usize) -> Res<Tensor<T>> {
+        Ok(Tensor {
+            data: Arc::clone(&self.data),
+            shape: self.shape.narrow(dimension, start, length)?,
+        })
+    }
+
+    pub fn split(&self, size: usize, dimension: usize) -> Res<Vec<Tensor<T>>> {
+        self.shape.valid_dimensions(&[dimension])?;
+        if size == 0 {
+            return Err(SplitError::Size.into());
+        }
+
+        let length = self.sizes()[dimension];
+        (0..length)
+            .step_by(size)
+            .map(|start| self.narrow(dimension, start, size.min(length - start)))
+            .collect()
+    }
+
+    pub fn chunk(&self, chunks: usize, dimension: usize) -> Res<Vec<Tensor<T>>> {
+        self.shape.valid_dimensions(&[dimension])?;
+        if chunks == 0 {
+            return Err(SplitError::Chunks.into());
+        }
+
+        self.split(self.sizes()[dimension].div_ceil(chunks).max(1), dimension)
+    }
+
+    pub fn single_slice(&self, dimension: usize, index: usize) -> Res<Tensor<T>> {
+        Ok(Tensor {
+            data: Arc::clone(&self.data),
+            shape: self.shape.single_slice(dimension, index)?,
+        })
+    }
+
+    pub fn select(&self, dimension: usize, index: usize) -> Res<Tensor<T>> {
+        self.single_slice(dimension, index)?.squeeze_dim(dimension)
+    }
+
     pub(crate) fn slicer(&self, indices: &[Option<usize>]) -> Res<Tensor<T>> {
         Ok(Tensor {
             data: Arc::clone(&self.data),
@@ -581,6 +1351,13 @@ impl<T> Tensor<T> {
         })
     }
 
+    pub fn broadcast_shapes(shapes: &[&[usize]]) -> Res<Vec<usize>> {
+        shapes
+            .iter()
+            .try_fold(Vec::new(), |sizes, shape| Shape::broadcast(&sizes, shape))
+            .map_err(Into::into)
+    }
+
     // --- Attributes ---
 
     pub fn numel(&self) -> usize {
@@ -608,6 +1385,17 @@ impl<T> Tensor<T> {
     }
 }
 
+/// Splits `data` into `parts` equal consecutive chunks.
+fn nest<T: Copy>(data: &[T], parts: usize) -> Vec<Vec<T>> {
+    let length = data.len().checked_div(parts).unwrap_or(0);
+
+    (0..parts)
+        .map(|part| data[part * length..(part + 1) * length].to_vec())
+        .collect()
+}
+
+/// Strict equality of the underlying buffers and layouts (sizes and strides).
+/// Use `Tensor::equal` to compare logical values regardless of layout.
 impl<T: Copy + PartialEq> PartialEq for Tensor<T> {
     fn eq(&self, rhs: &Tensor<T>) -> bool {
         self.data == rhs.data && self.shape == rhs.shape
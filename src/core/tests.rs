@@ -1,6 +1,38 @@
 #[cfg(test)]
 mod core_tests {
-    use crate::{core::utils::Res, Tensor};
+    use crate::{
+        core::{iters::Indexer, utils::Res},
+        pad_sequence, sequence_mask, BorderMode, IndexMode, Tensor, Zero,
+    };
+    use std::{ops::Add, sync::Arc};
+
+    /// Element type whose `Default` is not its additive identity.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Offset(i32);
+
+    impl Default for Offset {
+        fn default() -> Self {
+            Offset(-1)
+        }
+    }
+
+    impl Add for Offset {
+        type Output = Offset;
+
+        fn add(self, rhs: Offset) -> Offset {
+            Offset(self.0 + rhs.0)
+        }
+    }
+
+    impl Zero for Offset {
+        fn zero() -> Self {
+            Offset(0)
+        }
+
+        fn is_zero(&self) -> bool {
+            self.0 == 0
+        }
+    }
 
     #[test]
     fn same_memory() -> Res<()> {
@@ -80,4 +112,2718 @@ mod core_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn clamp_tensor() -> Res<()> {
+        let tensor = Tensor::arange(0, 12, 1)?.view(&[3, 4])?;
+        let min = Tensor::new_1d(&[2, 0, 5, 1])?;
+        let max = Tensor::new_1d(&[6, 3, 9, 1])?;
+
+        let clamped = tensor.clamp_tensor(&min, &max)?;
+        let expected = Tensor::new(
+            &[
+                2, 1, 5, 1, //
+                4, 3, 6, 1, //
+                6, 3, 9, 1, //
+            ],
+            &[3, 4],
+        )?;
+
+        assert_eq!(clamped, expected);
+        assert!(tensor.clamp_tensor(&Tensor::ones(3)?, &max).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn is_contiguous_strided() -> Res<()> {
+        use crate::core::shape::Stride;
+
+        let vector = Tensor::arange(0, 6, 1)?;
+        assert!(vector.is_contiguous());
+
+        let stepped = vector.view(&[3, 2])?.select(1, 0)?;
+        assert!(matches!(stepped.strides(), [Stride::Positive(2)]));
+        assert!(!stepped.is_contiguous());
+        assert_eq!(stepped.data(), vec![0, 2, 4]);
+
+        let column = vector.view(&[2, 3])?.select(1, 1)?;
+        assert!(matches!(column.strides(), [Stride::Positive(3)]));
+        assert!(!column.is_contiguous());
+        assert_eq!(column.data(), vec![1, 4]);
+
+        assert!(vector.flip(&[0])?.is_contiguous());
+        assert!(Tensor::new(&[1], &[])?.is_contiguous());
+
+        Ok(())
+    }
+
+    #[test]
+    fn select() -> Res<()> {
+        use std::sync::Arc;
+
+        let tensor = Tensor::arange(0, 12, 1)?.view(&[3, 4])?;
+        let row = tensor.select(0, 2)?;
+        let column = tensor.select(1, 1)?;
+
+        assert_eq!(row.sizes(), &[4]);
+        assert_eq!(row.data(), vec![8, 9, 10, 11]);
+        assert_eq!(column.data(), vec![1, 5, 9]);
+        assert_eq!(Arc::as_ptr(&tensor.data), Arc::as_ptr(&row.data));
+
+        assert!(tensor.select(0, 3).is_err());
+        assert!(tensor.select(2, 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn put_along_dim() -> Res<()> {
+        let tensor = Tensor::new(&[3, 1, 7, 9, 2, 4], &[2, 3])?;
+        let maxima = tensor.max_dims(&[1], true)?;
+        let indices = Tensor::new(&[2, 0], &[2, 1])?;
+
+        let zeroes = Tensor::zeroes(6)?.view(&[2, 3])?;
+        let scattered = zeroes.put_along_dim(&indices, &maxima, 1)?;
+
+        assert_eq!(scattered.data(), vec![0, 0, 7, 9, 0, 0]);
+
+        let out_of_range = Tensor::new(&[3, 0], &[2, 1])?;
+        assert!(zeroes.put_along_dim(&out_of_range, &maxima, 1).is_err());
+        assert!(zeroes.put_along_dim(&indices, &tensor, 1).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn reduce_with_index() -> Res<()> {
+        let tensor = Tensor::new(&[3, 9, 7, 9, 2, 4], &[2, 3])?;
+
+        let max_with_index = |slice: &Tensor<i32>| {
+            let (index, max) =
+                slice
+                    .data()
+                    .into_iter()
+                    .enumerate()
+                    .fold(
+                        (0, i32::MIN),
+                        |acc, (i, elem)| if elem > acc.1 { (i, elem) } else { acc },
+                    );
+
+            Ok((max, index))
+        };
+
+        let (max, argmax) = tensor.reduce_with_index(&[1], max_with_index, true)?;
+
+        assert_eq!(max.sizes(), &[2, 1]);
+        assert_eq!(max.data(), vec![9, 9]);
+        assert_eq!(argmax.data(), vec![1, 0]);
+        assert_eq!(max, tensor.max_dims(&[1], true)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn zip_with() -> Res<()> {
+        let tensor = Tensor::new(&[1.5, -2.0, 3.0, 4.5, 0.5, -1.0], &[2, 3])?;
+        let mask = Tensor::new_1d(&[true, false, true])?;
+
+        let masked = tensor.zip_with(&mask, |elem, keep| if keep { elem } else { 0.0 })?;
+
+        assert_eq!(masked.sizes(), &[2, 3]);
+        assert_eq!(masked.data(), vec![1.5, 0.0, 3.0, 4.5, 0.0, -1.0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn broadcast_shapes() -> Res<()> {
+        let sizes = Tensor::<f32>::broadcast_shapes(&[&[8, 1, 3], &[4, 1], &[1, 3]])?;
+        assert_eq!(sizes, vec![8, 4, 3]);
+
+        let sizes = Tensor::<f32>::broadcast_shapes(&[&[5], &[2, 1, 1]])?;
+        assert_eq!(sizes, vec![2, 1, 5]);
+
+        assert!(Tensor::<f32>::broadcast_shapes(&[&[2, 3], &[3], &[4, 1]]).is_err());
+        assert!(Tensor::<f32>::broadcast_shapes(&[&[2, 3], &[2]]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn unsqueeze_keeps_strides() -> Res<()> {
+        let tensor = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+
+        let transposed = tensor.transpose(0, 1)?;
+        let unsqueezed = transposed.unsqueeze(3)?;
+        assert_eq!(unsqueezed.sizes(), &[1, 3, 2]);
+        assert_eq!(unsqueezed.data(), transposed.data());
+
+        let sliced = tensor.slice(&[(1, 2), (0, 3)])?.unsqueeze(3)?;
+        assert_eq!(sliced.data(), vec![3, 4, 5]);
+
+        let ones = Tensor::ones(6)?.view(&[1, 3, 2])?;
+        assert_eq!((&transposed + &ones)?.data(), vec![1, 4, 2, 5, 3, 6]);
+        assert_eq!(
+            unsqueezed.expand(&[2, 3, 2])?.data(),
+            [0, 3, 1, 4, 2, 5].repeat(2)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn atleast() -> Res<()> {
+        use std::sync::Arc;
+
+        let scalar = Tensor::scalar(5)?;
+        assert_eq!(scalar.atleast_2d()?.sizes(), &[1, 1]);
+        assert_eq!(scalar.atleast_3d()?.sizes(), &[1, 1, 1]);
+
+        let vector = Tensor::new_1d(&[1, 2, 3])?;
+        let vector_2d = vector.atleast_2d()?;
+        let vector_3d = vector.atleast_3d()?;
+
+        assert_eq!(vector.atleast_1d()?.sizes(), &[3]);
+        assert_eq!(vector_2d.sizes(), &[1, 3]);
+        assert_eq!(vector_3d.sizes(), &[1, 1, 3]);
+        assert_eq!(vector_3d.data(), vec![1, 2, 3]);
+        assert_eq!(Arc::as_ptr(&vector.data), Arc::as_ptr(&vector_3d.data));
+
+        let flipped = Tensor::arange(0, 6, 1)?.view(&[2, 3])?.flip(&[1])?;
+        assert_eq!(flipped.atleast_3d()?.data(), vec![2, 1, 0, 5, 4, 3]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn squeeze_all_but() -> Res<()> {
+        let tensor = Tensor::arange(0, 12, 1)?.view(&[1, 3, 1, 4, 1])?;
+
+        let squeezed = tensor.squeeze_all_but(&[2])?;
+        assert_eq!(squeezed.sizes(), &[3, 1, 4]);
+        assert_eq!(squeezed.data(), tensor.data());
+
+        assert_eq!(tensor.squeeze_all_but(&[])?.sizes(), &[3, 4]);
+        assert_eq!(tensor.squeeze_all_but(&[0, 4])?.sizes(), &[1, 3, 4, 1]);
+        assert!(tensor.squeeze_all_but(&[5]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn expand_as() -> Res<()> {
+        use crate::core::shape::Stride;
+
+        let tensor = Tensor::new(&[1, 2, 3, 4], &[1, 4])?;
+        let reference = Tensor::<i32>::zeroes(12)?.view(&[3, 4])?;
+
+        let expanded = tensor.expand_as(&reference)?;
+        assert_eq!(expanded.sizes(), &[3, 4]);
+        assert!(expanded.strides()[0] == Stride::Positive(0));
+        assert_eq!(expanded.data(), [1, 2, 3, 4].repeat(3));
+
+        let vector = Tensor::new_1d(&[1, 2, 3])?;
+        assert!(vector.expand_as(&reference).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn reshape_as() -> Res<()> {
+        let tensor = Tensor::arange(0, 12, 1)?.view(&[2, 6])?;
+        let reference = Tensor::<i32>::zeroes(12)?.view(&[3, 4])?;
+
+        let reshaped = tensor.reshape_as(&reference)?;
+        assert_eq!(reshaped.sizes(), &[3, 4]);
+        assert_eq!(reshaped.data(), tensor.data());
+
+        let transposed = tensor.transpose(0, 1)?.reshape_as(&reference)?;
+        assert_eq!(transposed.sizes(), &[3, 4]);
+        assert_eq!(
+            transposed.data(),
+            vec![0, 6, 1, 7, 2, 8, 3, 9, 4, 10, 5, 11]
+        );
+
+        assert!(tensor.reshape_as(&Tensor::zeroes(10)?).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn nan_reductions() -> Res<()> {
+        let nan = f64::NAN;
+        let tensor = Tensor::new(&[1.0, nan, 3.0, nan, -2.0, 6.0], &[2, 3])?;
+
+        assert!(tensor.sum()?.is_nan());
+        assert_eq!(tensor.nansum()?, 8.0);
+        assert_eq!(tensor.nanmean()?, 2.0);
+        assert_eq!(tensor.nanmax()?, 6.0);
+        assert_eq!(tensor.nanmin()?, -2.0);
+
+        let row_means = tensor.nanmean_dims(&[1], true)?;
+        assert_eq!(row_means.data(), vec![2.0, 2.0]);
+
+        let all_nan = Tensor::new_1d(&[nan, nan])?;
+        assert!(all_nan.nanmax().is_err());
+        assert!(all_nan.nanmean()?.is_nan());
+
+        Ok(())
+    }
+
+    #[test]
+    fn diagflat() -> Res<()> {
+        let values = Tensor::new(&[1, 2, 3, 4], &[2, 2])?;
+        let diagonal = Tensor::diagflat(&values)?;
+
+        let expected = Tensor::new(
+            &[
+                1, 0, 0, 0, //
+                0, 2, 0, 0, //
+                0, 0, 3, 0, //
+                0, 0, 0, 4, //
+            ],
+            &[4, 4],
+        )?;
+
+        assert_eq!(diagonal, expected);
+        assert_eq!(Tensor::diagflat(&Tensor::<u8>::ones(3)?)?, Tensor::eye(3)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn block_diag() -> Res<()> {
+        let a = Tensor::new(&[1, 2, 3, 4], &[2, 2])?;
+        let b = Tensor::new(&[5, 6, 7], &[1, 3])?;
+        let block = Tensor::block_diag(&[&a, &b])?;
+
+        let expected = Tensor::new(
+            &[
+                1, 2, 0, 0, 0, //
+                3, 4, 0, 0, 0, //
+                0, 0, 5, 6, 7, //
+            ],
+            &[3, 5],
+        )?;
+
+        assert_eq!(block, expected);
+        assert!(Tensor::block_diag(&[&a, &Tensor::ones(3)?]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn broadcast_repeat() -> Res<()> {
+        use crate::core::shape::Stride;
+        use std::sync::Arc;
+
+        let tensor = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+        let repeated = tensor.broadcast_repeat(1, 4)?;
+
+        assert_eq!(repeated.sizes(), &[2, 4, 3]);
+        assert!(repeated.strides()[1] == Stride::Positive(0));
+        assert_eq!(Arc::as_ptr(&tensor.data), Arc::as_ptr(&repeated.data));
+        assert_eq!(
+            repeated.data(),
+            [[0, 1, 2].repeat(4), [3, 4, 5].repeat(4)].concat()
+        );
+
+        assert_eq!(tensor.broadcast_repeat(2, 2)?.sizes(), &[2, 3, 2]);
+        assert!(tensor.broadcast_repeat(3, 2).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn sum_kahan() -> Res<()> {
+        let tensor = Tensor::<f32>::same(0.1, 1_000_000)?;
+        let reference = 0.1_f32 as f64 * 1_000_000.0;
+
+        let naive_error = (tensor.sum()? as f64 - reference).abs();
+        let kahan_error = (tensor.sum_kahan()? as f64 - reference).abs();
+
+        assert!(kahan_error < naive_error);
+        assert!(kahan_error < 1e-2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn slicer_negative_strides() -> Res<()> {
+        let tensor = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+
+        let flipped = tensor.flip(&[0, 1])?;
+        assert_eq!(flipped.sum_dims(&[1], true)?.data(), vec![12, 3]);
+        assert_eq!(flipped.max_dims(&[0], true)?.data(), vec![5, 4, 3]);
+
+        let rows = tensor.flip(&[0])?;
+        assert_eq!(rows.sum_dims(&[1], true)?.data(), vec![12, 3]);
+        assert_eq!(rows.max_dims(&[1], true)?.data(), vec![5, 2]);
+
+        let columns = tensor.flip(&[1])?;
+        assert_eq!(columns.product_dims(&[0], true)?.data(), vec![10, 4, 0]);
+        assert_eq!(
+            columns.transpose(0, 1)?.sum_dims(&[1], true)?.data(),
+            vec![7, 5, 3]
+        );
+
+        let stepped = Tensor::arange(0, 10, 1)?
+            .view(&[5, 2])?
+            .select(1, 0)?
+            .flip(&[0])?;
+        assert_eq!(stepped.data(), vec![8, 6, 4, 2, 0]);
+        assert_eq!(
+            stepped.unfold(0, 2, 1)?.sum_dims(&[1], true)?.data(),
+            vec![14, 10, 6, 2]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn moving_average() -> Res<()> {
+        let ramp = Tensor::<f64>::arange(0.0, 10.0, 1.0)?;
+        let average = ramp.moving_average(3, 0)?;
+
+        assert_eq!(average.sizes(), &[8]);
+        assert_eq!(average.data(), Vec::from_iter((1..9).map(f64::from)));
+
+        let matrix = Tensor::<f64>::arange(0.0, 8.0, 1.0)?.view(&[2, 4])?;
+        let transposed_average = matrix.transpose(0, 1)?.moving_average(2, 0)?;
+        assert_eq!(transposed_average.sizes(), &[3, 2]);
+        assert_eq!(
+            transposed_average.data(),
+            vec![0.5, 4.5, 1.5, 5.5, 2.5, 6.5]
+        );
+
+        assert!(ramp.moving_average(11, 0).is_err());
+        assert!(ramp.moving_average(0, 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn data_flipped() -> Res<()> {
+        let tensor = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+
+        let flipped = tensor.flip(&[0, 1])?;
+        assert!(flipped.is_contiguous());
+        assert_eq!(flipped.data(), vec![5, 4, 3, 2, 1, 0]);
+        assert_eq!(flipped.index(&[0, 0])?, 5);
+
+        let sliced = Tensor::arange(0, 8, 1)?.slice(&[(2, 6)])?.flip(&[0])?;
+        assert_eq!(sliced.data(), vec![5, 4, 3, 2]);
+
+        assert_eq!(tensor.flip(&[1])?.data(), vec![2, 1, 0, 5, 4, 3]);
+        assert_eq!(flipped.flip(&[0, 1])?.data(), tensor.data());
+
+        Ok(())
+    }
+
+    #[test]
+    fn digitize() -> Res<()> {
+        let bins = Tensor::new_1d(&[1.0, 2.0, 4.0])?;
+        let values = Tensor::new_1d(&[0.5, 1.0, 3.0, 4.0, 5.0])?;
+
+        let left = values.digitize(&bins, false)?;
+        let right = values.digitize(&bins, true)?;
+
+        assert_eq!(left.data(), vec![0, 1, 2, 3, 3]);
+        assert_eq!(right.data(), vec![0, 0, 2, 2, 3]);
+
+        let decreasing = Tensor::new_1d(&[4.0, 2.0, 1.0])?;
+        assert_eq!(
+            values.digitize(&decreasing, false)?.data(),
+            vec![3, 2, 1, 0, 0]
+        );
+
+        let unsorted = Tensor::new_1d(&[1.0, 4.0, 2.0])?;
+        assert!(values.digitize(&unsorted, false).is_err());
+        assert!(values.digitize(&bins.view(&[3, 1])?, false).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn outer_map() -> Res<()> {
+        let lhs = Tensor::new_1d(&[1, 4, 2])?;
+        let rhs = Tensor::new_1d(&[3, 0, 2, 5])?;
+
+        let outer_min = lhs.outer_map(&rhs, i32::min)?;
+        let expected = Tensor::new(
+            &[
+                1, 0, 1, 1, //
+                3, 0, 2, 4, //
+                2, 0, 2, 2, //
+            ],
+            &[3, 4],
+        )?;
+
+        assert_eq!(outer_min, expected);
+
+        let matrix = Tensor::arange(0, 4, 1)?.view(&[2, 2])?;
+        assert_eq!(matrix.outer_map(&rhs, |l, r| l + r)?.sizes(), &[2, 2, 4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_coo() -> Res<()> {
+        let tensor = Tensor::new(&[0, 3, 0, 0, 0, 5, 7, 0, 0], &[3, 3])?;
+
+        let indices = tensor.nonzero()?;
+        assert_eq!(indices.sizes(), &[3, 2]);
+        assert_eq!(indices.data(), vec![0, 1, 1, 2, 2, 0]);
+
+        let values = Tensor::new_1d(&[3, 5, 7])?;
+        let dense = Tensor::from_coo(&indices, &values, &[3, 3], 0)?;
+        assert_eq!(dense, tensor);
+
+        let out_of_range = Tensor::new(&[3, 0], &[1, 2])?;
+        let value = Tensor::new_1d(&[1])?;
+        assert!(Tensor::from_coo(&out_of_range, &value, &[3, 3], 0).is_err());
+        assert!(Tensor::from_coo(&indices, &value, &[3, 3], 0).is_err());
+        assert!(Tensor::from_coo(&indices, &values, &[3, 3, 3], 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn trapz() -> Res<()> {
+        let x = Tensor::<f64>::arange(0.0, 5.0, 1.0)?;
+        let y = ((&x * 2.0)? + 1.0)?;
+
+        let area = y.trapz(1.0, 0)?;
+        assert!(area.sizes().is_empty());
+        assert_eq!(area.data(), vec![20.0]);
+
+        let rows = y.view(&[1, 5])?.expand(&[2, 5])?;
+        let row_areas = rows.trapz(0.5, 1)?;
+        assert_eq!(row_areas.sizes(), &[2]);
+        assert_eq!(row_areas.data(), vec![10.0, 10.0]);
+        assert_eq!(rows.trapz(1.0, 0)?.sizes(), &[5]);
+
+        let x = Tensor::new_1d(&[0.0, 1.0, 3.0, 4.0])?;
+        let y = ((&x * 2.0)? + 1.0)?;
+        let area = y.trapz_x(&x, 0)?;
+        assert!(area.sizes().is_empty());
+        assert_eq!(area.data(), vec![20.0]);
+        assert!(y.trapz_x(&Tensor::ones(3)?, 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn correlate_same() -> Res<()> {
+        use crate::conv::Mode;
+
+        let image = Tensor::<f64>::arange(0.0, 20.0, 1.0)?.view(&[4, 5])?;
+        let box_filter = Tensor::<f64>::ones(9)?.view(&[3, 3])?;
+
+        let padded = image.pad(0.0, &[(1, 1), (1, 1)])?;
+        let reference = Indexer::new(&[4, 5])
+            .map(|index| {
+                let window = padded.slice(&[(index[0], index[0] + 3), (index[1], index[1] + 3)])?;
+                window.sum()
+            })
+            .collect::<Res<Vec<f64>>>()?;
+
+        let blurred = image.correlate_2d(&box_filter, &[1, 1], Mode::Same)?;
+        assert_eq!(blurred.sizes(), &[4, 5]);
+        assert_eq!(blurred.data(), reference);
+
+        let signal = Tensor::<f64>::arange(0.0, 6.0, 1.0)?;
+        let kernel = Tensor::new_1d(&[1.0, 2.0, 3.0, 4.0, 5.0])?;
+
+        let same = signal.correlate_1d(&kernel, &[1], Mode::Same)?;
+        assert_eq!(same.data(), vec![14.0, 26.0, 40.0, 55.0, 40.0, 26.0]);
+
+        let valid = signal.correlate_1d(&kernel, &[1], Mode::Valid)?;
+        assert_eq!(valid.data(), vec![40.0, 55.0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn shift() -> Res<()> {
+        let tensor = Tensor::arange(1, 7, 1)?;
+
+        assert_eq!(tensor.shift(2, 0, 0)?.data(), vec![0, 0, 1, 2, 3, 4]);
+        assert_eq!(tensor.shift(-1, 0, 0)?.data(), vec![2, 3, 4, 5, 6, 0]);
+        assert_eq!(tensor.shift(7, 0, 0)?.data(), vec![0; 6]);
+
+        let matrix = tensor.view(&[2, 3])?;
+        assert_eq!(matrix.shift(1, 1, -1)?.data(), vec![-1, 1, 2, -1, 4, 5]);
+        assert!(matrix.transpose(0, 1)?.shift(1, 0, 0)?.is_contiguous());
+        assert!(matrix.shift(1, 2, 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn tile_to() -> Res<()> {
+        let tensor = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+        let tiled = tensor.tile_to(&[4, 6])?;
+
+        let expected = Tensor::new(
+            &[
+                0, 1, 2, 0, 1, 2, //
+                3, 4, 5, 3, 4, 5, //
+                0, 1, 2, 0, 1, 2, //
+                3, 4, 5, 3, 4, 5, //
+            ],
+            &[4, 6],
+        )?;
+
+        assert_eq!(tiled, expected);
+        assert!(tiled.is_contiguous());
+        assert_eq!(tensor.tile_to(&[2, 2, 3])?.sizes(), &[2, 2, 3]);
+        assert!(tensor.tile_to(&[5, 6]).is_err());
+        assert!(tensor.tile_to(&[6]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn pad_to() -> Res<()> {
+        use crate::PadAlign;
+
+        let tensor = Tensor::new_1d(&[1, 2, 3])?;
+
+        let before = tensor.pad_to(&[5], 0, PadAlign::Before)?;
+        let after = tensor.pad_to(&[5], 0, PadAlign::After)?;
+        let center = tensor.pad_to(&[5], 0, PadAlign::Center)?;
+        let uneven = tensor.pad_to(&[6], 0, PadAlign::Center)?;
+
+        assert_eq!(before.data(), vec![0, 0, 1, 2, 3]);
+        assert_eq!(after.data(), vec![1, 2, 3, 0, 0]);
+        assert_eq!(center.data(), vec![0, 1, 2, 3, 0]);
+        assert_eq!(uneven.data(), vec![0, 1, 2, 3, 0, 0]);
+
+        assert!(tensor.pad_to(&[2], 0, PadAlign::After).is_err());
+        assert!(tensor.pad_to(&[5, 5], 0, PadAlign::After).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn trim() -> Res<()> {
+        use std::sync::Arc;
+
+        let core = Tensor::arange(1, 7, 1)?.view(&[2, 3])?;
+        let padded = core.pad(0, &[(1, 2), (2, 0)])?;
+        assert_eq!(padded.sizes(), &[5, 5]);
+
+        let trimmed = padded.trim(0)?;
+        assert_eq!(trimmed.sizes(), &[2, 3]);
+        assert_eq!(trimmed.data(), core.data());
+        assert_eq!(Arc::as_ptr(&padded.data), Arc::as_ptr(&trimmed.data));
+
+        let flipped = padded.flip(&[1])?.trim(0)?;
+        assert_eq!(flipped.data(), core.flip(&[1])?.data());
+
+        let zeroes = Tensor::<i32>::zeroes(4)?.trim(0)?;
+        assert_eq!(zeroes.numel(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rot90() -> Res<()> {
+        let tensor = Tensor::arange(0, 12, 1)?.view(&[3, 4])?;
+
+        let rot_1 = tensor.rot90(1, (0, 1))?;
+        let rot_2 = tensor.rot90(2, (0, 1))?;
+        let rot_3 = tensor.rot90(3, (0, 1))?;
+        let rot_4 = tensor.rot90(4, (0, 1))?;
+
+        assert_eq!(rot_1.sizes(), &[4, 3]);
+        assert_eq!(rot_1.data(), vec![3, 7, 11, 2, 6, 10, 1, 5, 9, 0, 4, 8]);
+        assert_eq!(rot_2.data(), Vec::from_iter((0..12).rev()));
+        assert_eq!(rot_3.data(), vec![8, 4, 0, 9, 5, 1, 10, 6, 2, 11, 7, 3]);
+        assert_eq!(rot_4.data(), tensor.data());
+        assert_eq!(tensor.rot90(-1, (0, 1))?.data(), rot_3.data());
+
+        assert!(tensor.rot90(1, (0, 0)).is_err());
+        assert!(tensor.rot90(1, (0, 2)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fliplr_flipud() -> Res<()> {
+        let tensor = Tensor::arange(0, 24, 1)?.view(&[2, 3, 4])?;
+
+        assert_eq!(tensor.fliplr()?.data(), tensor.flip(&[2])?.data());
+        assert_eq!(tensor.flipud()?.data(), tensor.flip(&[0])?.data());
+
+        let vector = Tensor::new_1d(&[1, 2, 3])?;
+        assert_eq!(vector.flipud()?.data(), vec![3, 2, 1]);
+        assert!(vector.fliplr().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn maximum_minimum() -> Res<()> {
+        let a = Tensor::new(&[1, 5, 3, 8, 2, 6], &[2, 3])?;
+        let b = Tensor::new(&[4, 2, 3, 7, 9, 0], &[2, 3])?;
+
+        assert_eq!(a.maximum(&b)?.data(), vec![4, 5, 3, 8, 9, 6]);
+        assert_eq!(a.minimum(&b)?.data(), vec![1, 2, 3, 7, 2, 0]);
+
+        let row = Tensor::new_1d(&[3, 3, 7])?;
+        assert_eq!(a.maximum(&row)?.data(), vec![3, 5, 7, 8, 3, 7]);
+        assert_eq!(a.minimum(&row)?.data(), vec![1, 3, 3, 3, 2, 6]);
+
+        let relu = Tensor::new_1d(&[-1.5, 0.0, 2.5])?.maximum(&Tensor::scalar(0.0)?)?;
+        assert_eq!(relu.data(), vec![0.0, 0.0, 2.5]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn lerp() -> Res<()> {
+        let start = Tensor::new_1d(&[0.0, 2.0, -4.0])?;
+        let end = Tensor::new_1d(&[1.0, 6.0, 4.0])?;
+
+        assert_eq!(start.lerp(&end, 0.5)?.data(), vec![0.5, 4.0, 0.0]);
+        assert_eq!(start.lerp(&end, 0.0)?.data(), start.data());
+
+        let weight = Tensor::new(&[0.0, 1.0], &[2, 1])?;
+        let interpolated = start.lerp_tensor(&end, &weight)?;
+        assert_eq!(interpolated.sizes(), &[2, 3]);
+        assert_eq!(interpolated.data(), [start.data(), end.data()].concat());
+
+        Ok(())
+    }
+
+    #[test]
+    fn ema_update() -> Res<()> {
+        let mut average = Tensor::<f64>::zeroes(4)?.view(&[2, 2])?;
+        let snapshot = average.view(&[4])?;
+        let new = Tensor::new(&[1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        let decay = 0.5;
+
+        for _ in 0..3 {
+            average.ema_update(&new, decay)?;
+        }
+
+        let expected = (&new * (1.0 - f64::powi(decay, 3)))?;
+        assert_eq!(average.data(), expected.data());
+        assert_eq!(snapshot.data(), vec![0.0; 4]);
+
+        assert!(average.ema_update(&Tensor::ones(3)?, decay).is_err());
+
+        let base = Tensor::<f64>::zeroes(2)?.view(&[1, 2])?;
+        let mut expanded = base.expand(&[3, 2])?;
+        expanded.ema_update(&Tensor::ones(1)?, decay)?;
+        assert_eq!(expanded.data(), vec![0.5; 6]);
+        assert_eq!(base.data(), vec![0.0; 2]);
+
+        let mut windows = Tensor::<f64>::zeroes(4)?.unfold(0, 2, 1)?;
+        windows.ema_update(&Tensor::ones(1)?, decay)?;
+        assert_eq!(windows.data(), vec![0.5; 6]);
+
+        let mut transposed = Tensor::<f64>::zeroes(6)?.view(&[2, 3])?.transpose(0, 1)?;
+        transposed.ema_update(&new.narrow(0, 0, 1)?.narrow(1, 0, 1)?, decay)?;
+        assert_eq!(transposed.data(), vec![0.5; 6]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn clip_grad_value() -> Res<()> {
+        let gradients = Tensor::new_1d(&[-3.5, -0.5, 0.0, 1.0, 7.25])?;
+        let clipped = gradients.clip_grad_value(1.0)?;
+
+        assert_eq!(clipped.data(), vec![-1.0, -0.5, 0.0, 1.0, 1.0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn log_softmax() -> Res<()> {
+        let logits = Tensor::<f64>::new_1d(&[1.0, 2.0, 0.5, -1.0])?;
+
+        let log_softmax = logits.log_softmax(0)?;
+        let reference = logits.softmax()?.ln()?;
+        for (l, r) in log_softmax.data().into_iter().zip(reference.data()) {
+            assert!((l - r).abs() < 1e-12);
+        }
+
+        let large = Tensor::new_1d(&[1000.0, 1000.0])?.log_softmax(0)?;
+        assert!(large
+            .data()
+            .iter()
+            .all(|l| (l + 2.0_f64.ln()).abs() < 1e-12));
+
+        Ok(())
+    }
+
+    #[test]
+    fn softmax_backward() -> Res<()> {
+        let logits = Tensor::<f64>::new_1d(&[0.3, -1.2, 2.0])?;
+        let weights = Tensor::new_1d(&[1.0, -2.0, 0.5])?;
+        let loss = |x: &Tensor<f64>| (&x.log_softmax(0)?.exp()? * &weights)?.sum();
+
+        let output = logits.log_softmax(0)?.exp()?;
+        let gradient = Tensor::softmax_backward(&weights, &output, 0)?;
+
+        let epsilon = 1e-6;
+        for i in 0..3 {
+            let plus = logits.index_map(|x| x + epsilon, &[i])?;
+            let minus = logits.index_map(|x| x - epsilon, &[i])?;
+            let numerical = (loss(&plus)? - loss(&minus)?) / (2.0 * epsilon);
+
+            assert!((gradient.index(&[i])? - numerical).abs() < 1e-6);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn cross_entropy() -> Res<()> {
+        let logits = Tensor::<f64>::new(&[2.0, 1.0, 0.1, 0.5, 2.5, 0.3], &[2, 3])?;
+        let targets = Tensor::new_1d(&[0, 2])?;
+
+        let loss = Tensor::cross_entropy(&logits, &targets, 1)?;
+
+        let nll = |row: [f64; 3], target: usize| {
+            let sum = row.iter().map(|l| l.exp()).sum::<f64>();
+            -(row[target].exp() / sum).ln()
+        };
+        let expected = (nll([2.0, 1.0, 0.1], 0) + nll([0.5, 2.5, 0.3], 2)) / 2.0;
+
+        assert_eq!(loss.sizes(), &[1]);
+        assert!((loss.index(&[0])? - expected).abs() < 1e-12);
+
+        let invalid = Tensor::new_1d(&[0, 3])?;
+        assert!(Tensor::cross_entropy(&logits, &invalid, 1).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn losses() -> Res<()> {
+        use crate::Reduction;
+
+        let input = Tensor::<f64>::new(&[1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+        let target = Tensor::new(&[1.5, 2.0, 1.0, 5.0], &[2, 2])?;
+
+        let mse_none = input.mse_loss(&target, Reduction::None)?;
+        assert_eq!(mse_none.data(), vec![0.25, 0.0, 4.0, 1.0]);
+        assert_eq!(input.mse_loss(&target, Reduction::Sum)?.data(), vec![5.25]);
+        assert_eq!(
+            input.mse_loss(&target, Reduction::Mean)?.data(),
+            vec![1.3125]
+        );
+
+        let l1_none = input.l1_loss(&target, Reduction::None)?;
+        assert_eq!(l1_none.data(), vec![0.5, 0.0, 2.0, 1.0]);
+        assert_eq!(input.l1_loss(&target, Reduction::Sum)?.data(), vec![3.5]);
+        assert_eq!(input.l1_loss(&target, Reduction::Mean)?.data(), vec![0.875]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ones_eye_types() -> Res<()> {
+        assert_eq!(Tensor::<u8>::ones(3)?.data(), vec![1, 1, 1]);
+        assert_eq!(Tensor::<i64>::ones(2)?.data(), vec![1, 1]);
+        assert_eq!(Tensor::<f32>::ones(2)?.data(), vec![1.0, 1.0]);
+
+        assert_eq!(Tensor::<u8>::eye(2)?.data(), vec![1, 0, 0, 1]);
+        assert_eq!(Tensor::<i64>::eye(2)?.data(), vec![1, 0, 0, 1]);
+        assert_eq!(Tensor::<f32>::eye(2)?.data(), vec![1.0, 0.0, 0.0, 1.0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn zero_identity() -> Res<()> {
+        assert_eq!(u8::zero() + 7, 7);
+        assert_eq!(i64::zero() + -3, -3);
+        assert_eq!(f32::zero() + 2.5, 2.5);
+        assert_eq!(Offset::zero() + Offset(4), Offset(4));
+
+        let zeroes = Tensor::<Offset>::zeroes(2)?;
+        assert_eq!(zeroes.data(), vec![Offset(0); 2]);
+
+        let padded = Tensor::new_1d(&[Offset(4)])?.pad_zeroes(&[(1, 1)])?;
+        assert_eq!(padded.data(), vec![Offset(0), Offset(4), Offset(0)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sum_zero_identity() -> Res<()> {
+        let tensor = Tensor::new(&[Offset(1), Offset(2), Offset(3), Offset(4)], &[2, 2])?;
+
+        assert_eq!(tensor.sum()?, Offset(10));
+        assert_eq!(
+            tensor.sum_dims(&[1], true)?.data(),
+            vec![Offset(3), Offset(7)]
+        );
+        assert_eq!(Tensor::<Offset>::new_1d(&[])?.sum()?, Offset(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn widened_reductions() -> Res<()> {
+        let ones = Tensor::<u8>::ones(256)?;
+        assert_eq!(ones.sum_as::<u32>()?, 256);
+
+        let twos = Tensor::<u8>::same(2, 10)?;
+        assert_eq!(twos.product_as::<u64>()?, 1024);
+
+        Ok(())
+    }
+
+    #[test]
+    fn equal() -> Res<()> {
+        let tensor = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+        let copy = tensor.transpose(0, 1)?.to_contiguous()?.transpose(0, 1)?;
+
+        assert!(tensor.equal(&copy));
+        assert!(tensor != copy);
+
+        assert!(!tensor.equal(&tensor.view(&[3, 2])?));
+        assert!(!tensor.equal(&tensor.view(&[6])?));
+        assert!(!tensor.equal(&Tensor::new_1d(&[0, 1, 2])?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn approx_equal_report() -> Res<()> {
+        let expected = Tensor::<f64>::arange(0.0, 6.0, 1.0)?.view(&[2, 3])?;
+        let close = (&expected + 1e-9)?;
+        let perturbed = expected.index_map(|x| x + 0.5, &[1, 2])?;
+
+        assert!(close.allclose(&expected, 1e-6, 1e-6)?);
+        assert!(close.approx_equal_report(&expected, 1e-6, 1e-6)?.is_ok());
+
+        let report = perturbed.approx_equal_report(&expected, 1e-6, 1e-6)?;
+        assert!(report.is_err_and(|message| message.contains("[1, 2]")));
+
+        let with_nan = expected.index_map(|_| f64::NAN, &[0, 1])?;
+        assert!(!with_nan.allclose(&expected, 1e-6, 1e-6)?);
+        let report = with_nan.approx_equal_report(&expected, 1e-6, 1e-6)?;
+        assert!(report.is_err_and(|message| message.contains("[0, 1]")));
+        let report = expected.approx_equal_report(&with_nan, 1e-6, 1e-6)?;
+        assert!(report.is_err_and(|message| message.contains("NaN")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_nested_vec() -> Res<()> {
+        let tensor = Tensor::arange(0, 8, 1)?.view(&[2, 2, 2])?;
+
+        let nested = tensor.to_vec3()?;
+        assert_eq!(
+            nested,
+            vec![vec![vec![0, 1], vec![2, 3]], vec![vec![4, 5], vec![6, 7]]]
+        );
+
+        let transposed = tensor.transpose(0, 2)?.to_vec3()?;
+        assert_eq!(transposed[1][0], vec![1, 5]);
+
+        assert_eq!(
+            tensor.view(&[2, 4])?.to_vec2()?,
+            vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]
+        );
+        assert_eq!(tensor.view(&[1, 2, 2, 2])?.to_vec4()?, vec![nested]);
+        assert!(tensor.to_vec2().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn flat_map() -> Res<()> {
+        let tensor = Tensor::new_1d(&[1, 2, 3])?;
+        let features = tensor.flat_map(|x| [x, x * x])?;
+
+        assert_eq!(features.sizes(), &[3, 2]);
+        assert_eq!(features.data(), vec![1, 1, 2, 4, 3, 9]);
+
+        let flipped = tensor.view(&[3, 1])?.flip(&[0])?.flat_map(|x| [x; 3])?;
+        assert_eq!(flipped.sizes(), &[3, 1, 3]);
+        assert_eq!(flipped.data(), vec![3, 3, 3, 2, 2, 2, 1, 1, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bucketize() -> Res<()> {
+        let boundaries = Tensor::new_1d(&[1, 3, 5, 7, 9])?;
+        let values = Tensor::new(&[3, 6, 9, 3, 6, 9], &[2, 3])?;
+
+        let left = values.bucketize(&boundaries, false)?;
+        let right = values.bucketize(&boundaries, true)?;
+
+        assert_eq!(left.sizes(), &[2, 3]);
+        assert_eq!(left.data(), vec![1, 3, 4, 1, 3, 4]);
+        assert_eq!(right.data(), vec![2, 3, 5, 2, 3, 5]);
+
+        assert_eq!(left.data(), boundaries.searchsorted(&values, false)?.data());
+        assert_eq!(right.data(), boundaries.searchsorted(&values, true)?.data());
+
+        let unsorted = Tensor::new_1d(&[1, 5, 3])?;
+        assert!(values.bucketize(&unsorted, false).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn arithmetic_ops() -> Res<()> {
+        let matrix = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+        let row = Tensor::new_1d(&[10, 20, 30])?;
+
+        let sum = (&matrix + &row)?;
+        assert_eq!(sum.sizes(), &[2, 3]);
+        assert_eq!(sum.data(), vec![10, 21, 32, 13, 24, 35]);
+        assert_eq!((&row + &matrix)?.data(), sum.data());
+
+        let flipped = matrix.flip(&[1])?;
+        assert!(!flipped.is_contiguous());
+        assert_eq!((&flipped + 1)?.data(), vec![3, 2, 1, 6, 5, 4]);
+        assert_eq!((&flipped * &row)?.data(), vec![20, 20, 0, 50, 80, 90]);
+
+        assert!((&matrix - &Tensor::new_1d(&[1, 2])?).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn causal_shift() -> Res<()> {
+        let sequence = Tensor::new_1d(&[5, 6, 7, 8])?;
+        assert_eq!(sequence.causal_shift(0, 0)?.data(), vec![0, 5, 6, 7]);
+
+        let batch = Tensor::arange(1, 7, 1)?.view(&[2, 3])?;
+        assert_eq!(batch.causal_shift(1, -1)?.data(), vec![-1, 1, 2, -1, 4, 5]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn dimension_reductions() -> Res<()> {
+        let tensor = Tensor::<i64>::new(
+            &[
+                4, 9, 2, 7, //
+                3, 5, 8, 1, //
+                6, 0, 11, 10, //
+                //
+                13, 12, 17, 14, //
+                15, 16, 19, 18, //
+                20, 23, 21, 22, //
+            ],
+            &[2, 3, 4],
+        )?;
+
+        let data = tensor.data();
+        let columns = (0..2)
+            .flat_map(|b| (0..4).map(move |c| (b, c)))
+            .map(|(b, c)| {
+                (0..3)
+                    .map(|r| data[b * 12 + r * 4 + c])
+                    .collect::<Vec<i64>>()
+            })
+            .collect::<Vec<_>>();
+        let manual = |f: fn(&[i64]) -> i64| columns.iter().map(|c| f(c)).collect::<Vec<i64>>();
+
+        let sum = tensor.sum_dims(&[1], true)?;
+        let mean = tensor.mean_dims(&[1], true)?;
+        let max = tensor.max_dims(&[1], true)?;
+        let min = tensor.min_dims(&[1], true)?;
+
+        assert_eq!(sum.sizes(), &[2, 1, 4]);
+        assert_eq!(sum.data(), manual(|c| c.iter().sum()));
+        assert_eq!(mean.data(), manual(|c| c.iter().sum::<i64>() / 3));
+        assert_eq!(max.data(), manual(|c| *c.iter().max().unwrap_or(&0)));
+        assert_eq!(min.data(), manual(|c| *c.iter().min().unwrap_or(&0)));
+
+        let collapsed = tensor.sum_dims(&[1], false)?;
+        assert_eq!(collapsed.sizes(), &[2, 4]);
+        assert_eq!(collapsed.data(), sum.data());
+        assert_eq!(tensor.max_dims(&[0, 2], false)?.sizes(), &[3]);
+
+        let total = tensor.sum_dims(&[], true)?;
+        assert_eq!(total.sizes(), &[1, 1, 1]);
+        assert_eq!(total.data(), vec![tensor.sum()?]);
+        let collapsed = tensor.sum_dims(&[], false)?;
+        assert!(collapsed.sizes().is_empty());
+        assert_eq!(collapsed.data(), total.data());
+
+        let floats = tensor.unary_map(|elem| elem as f64 / 2.0)?;
+        assert_eq!(
+            floats.max_dims(&[1], true)?.data(),
+            max.unary_map(|elem| elem as f64 / 2.0)?.data()
+        );
+        assert_eq!(floats.min_dims(&[], true)?.data(), vec![0.0]);
+        assert_eq!(floats.transpose(0, 2)?.max()?, 11.5);
+
+        let with_nan = Tensor::new(&[1.0, f64::NAN, 3.0, -1.0, 2.0, 0.5], &[2, 3])?;
+        let row_max = with_nan.max_dims(&[1], true)?.data();
+        assert!(row_max[0].is_nan());
+        assert_eq!(row_max[1], 2.0);
+        assert!(with_nan.min()?.is_nan());
+        assert_eq!(with_nan.min_dims(&[0], true)?.data()[2], 0.5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn repeat() -> Res<()> {
+        let tensor = Tensor::new(&[1, 2, 3, 4, 5, 6], &[2, 3])?;
+
+        let repeated = tensor.repeat(&[2, 1, 2])?;
+        assert_eq!(repeated.sizes(), &[2, 2, 6]);
+        assert!(repeated.is_contiguous());
+        assert_eq!(
+            repeated.data(),
+            [1, 2, 3, 1, 2, 3, 4, 5, 6, 4, 5, 6].repeat(2)
+        );
+        assert!(repeated.equal(&tensor.tile_to(&[2, 2, 6])?));
+        assert!(tensor.repeat(&[2]).is_err());
+
+        let tiled = tensor.repeat(&[2, 1])?;
+        assert_eq!(tiled.sizes(), &[4, 3]);
+        assert_eq!(tiled.data(), vec![1, 2, 3, 4, 5, 6, 1, 2, 3, 4, 5, 6]);
+        assert!(tiled.is_contiguous());
+
+        let from_view = tensor.transpose(0, 1)?.flip(&[0])?.repeat(&[1, 2])?;
+        assert!(from_view.is_contiguous());
+        assert_eq!(from_view.data(), vec![3, 6, 3, 6, 2, 5, 2, 5, 1, 4, 1, 4]);
+        assert_eq!(from_view.view(&[12])?.data(), from_view.data());
+
+        let large = Tensor::arange(0, 1000, 1)?.repeat(&[1000])?;
+        assert_eq!(large.sizes(), &[1_000_000]);
+        assert_eq!(large.data.len(), 1_000_000);
+        assert_eq!(large.data.capacity(), 1_000_000);
+        assert_eq!(large.idx(&[999_999]), 999);
+
+        Ok(())
+    }
+
+    #[test]
+    fn argmax_argmin() -> Res<()> {
+        let tensor = Tensor::new(
+            &[
+                3, 7, 7, 1, //
+                5, 0, 9, 0, //
+            ],
+            &[2, 4],
+        )?;
+
+        assert_eq!(tensor.argmax(1, false)?.sizes(), &[2]);
+        assert_eq!(tensor.argmax(1, false)?.data(), vec![1, 2]);
+        assert_eq!(tensor.argmin(1, false)?.data(), vec![3, 1]);
+        assert_eq!(tensor.argmax(1, true)?.sizes(), &[2, 1]);
+        assert_eq!(tensor.argmax(1, true)?.data(), vec![1, 2]);
+        assert_eq!(tensor.argmax(0, true)?.sizes(), &[1, 4]);
+        assert_eq!(tensor.argmax(0, true)?.data(), vec![1, 0, 1, 0]);
+        assert_eq!(tensor.argmin(0, false)?.sizes(), &[4]);
+        assert_eq!(tensor.argmin(0, false)?.data(), vec![0, 1, 0, 1]);
+        assert_eq!(
+            tensor.argmax(1, false)?.sizes(),
+            tensor.max_dims(&[1], false)?.sizes()
+        );
+
+        let transposed = tensor.transpose(0, 1)?;
+        assert_eq!(transposed.argmax(0, false)?.sizes(), &[2]);
+        assert_eq!(transposed.argmax(0, false)?.data(), vec![1, 2]);
+        assert_eq!(transposed.argmin(0, false)?.data(), vec![3, 1]);
+        assert_eq!(transposed.argmax(1, true)?.sizes(), &[4, 1]);
+        assert_eq!(transposed.argmax(1, true)?.data(), vec![1, 0, 1, 0]);
+
+        assert!(tensor.argmax(2, false).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn matmul_ranks() -> Res<()> {
+        let vector = Tensor::new(&[1, 2, 3], &[3])?;
+        let matrix = Tensor::new(&[1, 2, 3, 4, 5, 6], &[3, 2])?;
+        let square = Tensor::new(&[1, 0, 2, 1], &[2, 2])?;
+
+        let dot = vector.matmul(&vector)?;
+        assert_eq!(dot.data(), vec![14]);
+
+        let row = vector.matmul(&matrix)?;
+        assert_eq!(row.sizes(), &[2]);
+        assert_eq!(row.data(), vec![22, 28]);
+
+        let column = matrix.transpose(0, 1)?.matmul(&vector)?;
+        assert_eq!(column.sizes(), &[2]);
+        assert_eq!(column.data(), vec![22, 28]);
+
+        let product = matrix.matmul(&square)?;
+        assert_eq!(product.sizes(), &[3, 2]);
+        assert_eq!(product.data(), vec![5, 2, 11, 4, 17, 6]);
+
+        let batched = Tensor::new(&[1, 2, 3, 4, 5, 6, 7, 8], &[2, 2, 2])?;
+        let batched_product = batched.matmul(&square)?;
+        assert_eq!(batched_product.sizes(), &[2, 2, 2]);
+        assert_eq!(batched_product.data(), vec![5, 2, 11, 4, 17, 6, 23, 8]);
+
+        assert!(vector.matmul(&batched).is_err());
+        assert!(batched.matmul(&vector).is_err());
+        assert!(vector.matmul(&Tensor::new(&[1, 2], &[2])?).is_err());
+        assert!(vector.matmul(&square).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn concat() -> Res<()> {
+        let lhs = Tensor::new(&[1, 2, 3, 4], &[2, 2])?;
+        let rhs = Tensor::new(&[5, 6, 7, 8, 9, 10], &[2, 3])?;
+
+        let single = Tensor::concat(&[&lhs], 0)?;
+        assert!(single.equal(&lhs));
+
+        let columns = Tensor::concat(&[&lhs, &rhs], 1)?;
+        assert_eq!(columns.sizes(), &[2, 5]);
+        assert_eq!(columns.data(), vec![1, 2, 5, 6, 7, 3, 4, 8, 9, 10]);
+
+        let rows = Tensor::concat(&[&lhs, &rhs.transpose(0, 1)?.narrow(0, 0, 2)?], 0)?;
+        assert_eq!(rows.sizes(), &[4, 2]);
+        assert_eq!(rows.data(), vec![1, 2, 3, 4, 5, 8, 6, 9]);
+        assert!(rows.is_contiguous());
+
+        let sliced = rhs.slice(&[(0, 2), (1, 3)])?;
+        let joined = Tensor::concat(&[&sliced, &lhs, &sliced], 1)?;
+        assert_eq!(joined.data(), vec![6, 7, 1, 2, 6, 7, 9, 10, 3, 4, 9, 10]);
+
+        let error = Tensor::concat(&[&lhs, &rhs], 0).err().unwrap();
+        assert!(error.to_string().contains("dimension 1"));
+        assert!(Tensor::<i32>::concat(&[], 0).is_err());
+        assert!(Tensor::concat(&[&lhs, &Tensor::new(&[1, 2], &[2])?], 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_merge_heads() -> Res<()> {
+        let tensor = Tensor::arange(0, 24, 1)?.view(&[2, 2, 6])?;
+
+        let heads = tensor.split_heads(3)?;
+        assert_eq!(heads.sizes(), &[2, 3, 2, 2]);
+        assert_eq!(
+            heads.single_slice(0, 0)?.data(),
+            vec![0, 1, 6, 7, 2, 3, 8, 9, 4, 5, 10, 11]
+        );
+
+        let merged = heads.merge_heads()?;
+        assert_eq!(merged.sizes(), &[2, 2, 6]);
+        assert!(merged.equal(&tensor));
+
+        assert!(tensor.split_heads(4).is_err());
+        assert!(tensor.split_heads(0).is_err());
+        assert!(tensor.view(&[4, 6])?.split_heads(2).is_err());
+        assert!(tensor.merge_heads().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn stack() -> Res<()> {
+        let a = Tensor::new(&[1, 2, 3, 4], &[2, 2])?;
+        let b = Tensor::new(&[5, 6, 7, 8], &[2, 2])?;
+        let c = Tensor::new(&[9, 10, 11, 12], &[2, 2])?;
+
+        let first = Tensor::stack(&[&a, &b, &c], 0)?;
+        assert_eq!(first.sizes(), &[3, 2, 2]);
+        assert_eq!(first.data(), (1..=12).collect::<Vec<i32>>());
+
+        let last = Tensor::stack(&[&a, &b, &c], 2)?;
+        assert_eq!(last.sizes(), &[2, 2, 3]);
+        assert_eq!(last.data(), vec![1, 5, 9, 2, 6, 10, 3, 7, 11, 4, 8, 12]);
+        assert!(last.is_contiguous());
+
+        assert!(Tensor::stack(&[&a, &b], 3).is_err());
+        assert!(Tensor::stack(&[&a, &Tensor::new(&[1, 2], &[2])?], 0).is_err());
+        assert!(Tensor::stack(&[&a, &a.transpose(0, 1)?], 1).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scaled_dot_product_attention() -> Res<()> {
+        let query = Tensor::<f64>::new(&[2.0, 0.0, 0.0, 2.0], &[2, 2])?;
+        let key = Tensor::new(&[1.0, 0.0, 0.0, 1.0], &[2, 2])?;
+        let value = Tensor::new(&[1.0, 2.0, 3.0, 4.0], &[2, 2])?;
+
+        // Scores are [[2, 0], [0, 2]] / sqrt(2), so each row softmaxes to [p, 1 - p].
+        let e = 2f64.sqrt().exp();
+        let p = e / (e + 1.0);
+        let expected = [
+            p + 3.0 * (1.0 - p),
+            2.0 * p + 4.0 * (1.0 - p),
+            (1.0 - p) + 3.0 * p,
+            2.0 * (1.0 - p) + 4.0 * p,
+        ];
+
+        let output = Tensor::scaled_dot_product_attention(&query, &key, &value, None)?;
+        assert_eq!(output.sizes(), &[2, 2]);
+        for (actual, expected) in output.data().into_iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-12);
+        }
+
+        let causal = Tensor::new(&[true, false, true, true], &[2, 2])?;
+        let masked = Tensor::scaled_dot_product_attention(&query, &key, &value, Some(&causal))?;
+        let masked_data = masked.data();
+        assert_eq!(&masked_data[..2], &[1.0, 2.0]);
+        assert!((masked_data[2] - expected[2]).abs() < 1e-12);
+
+        let batched = Tensor::scaled_dot_product_attention(
+            &query.unsqueeze(3)?,
+            &key.unsqueeze(3)?,
+            &value.unsqueeze(3)?,
+            None,
+        )?;
+        assert_eq!(batched.sizes(), &[1, 2, 2]);
+        assert!(batched.squeeze_dim(0)?.allclose(&output, 1e-12, 1e-12)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn layer_norm() -> Res<()> {
+        let tensor = Tensor::<f64>::new(
+            &[
+                1.0, 2.0, 3.0, 4.0, //
+                -2.0, 0.0, 5.0, 9.0, //
+                10.0, 10.0, 10.0, 14.0, //
+            ],
+            &[3, 4],
+        )?;
+
+        let normalized = tensor.layer_norm(&[1], None, None, 0.0)?;
+        let mean = normalized.mean_dims(&[1], true)?;
+        let variance = normalized
+            .unary_map(|elem| elem * elem)?
+            .mean_dims(&[1], true)?;
+
+        assert!(mean.data().iter().all(|&m| m.abs() < 1e-12));
+        assert!(variance.data().iter().all(|&v| (v - 1.0).abs() < 1e-12));
+
+        let weight = Tensor::new(&[1.0, 2.0, 3.0, 4.0], &[4])?;
+        let bias = Tensor::new(&[0.5, 0.5, 0.5, 0.5], &[4])?;
+        let affine = tensor.layer_norm(&[1], Some(&weight), Some(&bias), 0.0)?;
+        let expected = normalized.zip(&weight, |n, w| n * w + 0.5)?;
+        assert!(affine.allclose(&expected, 1e-12, 1e-12)?);
+
+        let batched = tensor
+            .view(&[3, 2, 2])?
+            .layer_norm(&[1, 2], None, None, 0.0)?;
+        assert!(batched
+            .reshape(&[3, 4])?
+            .allclose(&normalized, 1e-12, 1e-12)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_chunk() -> Res<()> {
+        let tensor = Tensor::arange(0, 14, 1)?.view(&[7, 2])?;
+
+        let splits = tensor.split(3, 0)?;
+        assert_eq!(splits.len(), 3);
+        assert_eq!(splits[0].data(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(splits[2].sizes(), &[1, 2]);
+        assert_eq!(splits[2].data(), vec![12, 13]);
+        assert!(splits
+            .iter()
+            .all(|split| Arc::ptr_eq(&split.data, &tensor.data)));
+
+        let chunks = tensor.transpose(0, 1)?.chunk(2, 1)?;
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].sizes(), &[2, 4]);
+        assert_eq!(chunks[1].data(), vec![8, 10, 12, 9, 11, 13]);
+        assert!(Arc::ptr_eq(&chunks[1].data, &tensor.data));
+
+        assert_eq!(tensor.chunk(10, 0)?.len(), 7);
+        assert!(tensor.split(0, 0).is_err());
+        assert!(tensor.chunk(0, 0).is_err());
+        assert!(tensor.split(1, 2).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn batch_norm_infer() -> Res<()> {
+        let tensor = Tensor::<f64>::arange(0.0, 12.0, 1.0)?.view(&[2, 3, 2])?;
+        let mean = Tensor::new(&[1.0, 2.0, 3.0], &[3])?;
+        let var = Tensor::new(&[4.0, 1.0, 0.25], &[3])?;
+        let weight = Tensor::new(&[1.0, 2.0, -1.0], &[3])?;
+        let bias = Tensor::new(&[0.0, 1.0, 0.5], &[3])?;
+
+        let output = tensor.batch_norm_infer(&mean, &var, Some(&weight), Some(&bias), 0.0)?;
+        let expected = Indexer::new(&[2, 3, 2])
+            .map(|index| {
+                let c = index[1];
+                let (m, v, w, b) = (
+                    mean.idx(&[c]),
+                    var.idx(&[c]),
+                    weight.idx(&[c]),
+                    bias.idx(&[c]),
+                );
+                (tensor.idx(&index) - m) / v.sqrt() * w + b
+            })
+            .collect::<Vec<f64>>();
+
+        assert_eq!(output.sizes(), &[2, 3, 2]);
+        assert!(output.allclose(&Tensor::new(&expected, &[2, 3, 2])?, 1e-12, 1e-12)?);
+
+        let unscaled = tensor.batch_norm_infer(&mean, &var, None, None, 0.0)?;
+        assert_eq!(unscaled.idx(&[1, 2, 1]), (11.0 - 3.0) / 0.5);
+
+        let short = Tensor::new(&[1.0, 2.0], &[2])?;
+        assert!(tensor
+            .batch_norm_infer(&short, &var, None, None, 0.0)
+            .is_err());
+        assert!(tensor
+            .batch_norm_infer(&mean, &var, Some(&short), None, 0.0)
+            .is_err());
+        assert!(mean.batch_norm_infer(&mean, &var, None, None, 0.0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn slice_step() -> Res<()> {
+        let vector = Tensor::arange(0, 6, 1)?;
+
+        let every_other = vector.slice_step(&[(0, 6, 2)])?;
+        assert_eq!(every_other.data(), vec![0, 2, 4]);
+        assert!(Arc::ptr_eq(&every_other.data, &vector.data));
+
+        assert_eq!(vector.slice_step(&[(1, 6, 2)])?.data(), vec![1, 3, 5]);
+        assert_eq!(vector.slice_step(&[(0, 0, 4)])?.data(), vec![0, 4]);
+        assert_eq!(vector.slice_step(&[(0, 6, -2)])?.data(), vec![5, 3, 1]);
+        assert_eq!(vector.slice_step(&[(0, 5, -2)])?.data(), vec![4, 2, 0]);
+        assert_eq!(
+            vector.flip_all()?.slice_step(&[(0, 6, 4)])?.data(),
+            vec![5, 1]
+        );
+        assert_eq!(
+            vector.flip_all()?.slice_step(&[(1, 6, -3)])?.data(),
+            vec![0, 3]
+        );
+
+        let matrix = Tensor::arange(0, 12, 1)?.view(&[4, 3])?;
+        let stepped = matrix.slice_step(&[(0, 4, 2), (0, 3, -2)])?;
+        assert_eq!(stepped.sizes(), &[2, 2]);
+        assert_eq!(stepped.data(), vec![2, 0, 8, 6]);
+
+        assert!(vector.slice_step(&[(0, 6, 0)]).is_err());
+        assert!(vector.slice_step(&[(0, 7, 1)]).is_err());
+        assert!(vector.slice_step(&[(0, 6, 1), (0, 1, 1)]).is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn dropout() -> Res<()> {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let tensor = Tensor::<f64>::ones(10_000)?.view(&[100, 100])?;
+
+        let dropped = tensor.dropout(0.25, true, &mut rng)?;
+        let data = dropped.data();
+        let zeros = data.iter().filter(|&&elem| elem == 0.0).count();
+
+        assert_eq!(dropped.sizes(), &[100, 100]);
+        assert!((zeros as f64 / 10_000.0 - 0.25).abs() < 0.02);
+        assert!(data
+            .iter()
+            .all(|&elem| elem == 0.0 || (elem - 1.0 / 0.75).abs() < 1e-12));
+
+        let again = tensor.dropout(0.25, true, &mut StdRng::seed_from_u64(0))?;
+        assert_eq!(again.data(), data);
+
+        assert!(tensor.dropout(0.25, false, &mut rng)?.equal(&tensor));
+        assert!(tensor.dropout(1.0, true, &mut rng).is_err());
+        assert!(tensor.dropout(-0.1, true, &mut rng).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn negative_axes() -> Res<()> {
+        let tensor = Tensor::arange(0, 24, 1)?.view(&[2, 3, 4])?;
+
+        let transposed = tensor.transpose_axis(-1, -2)?;
+        assert_eq!(transposed.sizes(), &[2, 4, 3]);
+        assert!(transposed.equal(&tensor.transpose(2, 1)?));
+
+        let permuted = tensor.permute_axes(&[-1, 0, -2])?;
+        assert_eq!(permuted.sizes(), &[4, 2, 3]);
+        assert!(permuted.equal(&tensor.permute(&[2, 0, 1])?));
+
+        let summed = tensor.reduce_axes(&[-1], Tensor::sum, true)?;
+        assert!(summed.equal(&tensor.sum_dims(&[2], true)?));
+
+        let squeezed = tensor.sum_dims(&[1], true)?.squeeze_axis(-2)?;
+        assert_eq!(squeezed.sizes(), &[2, 4]);
+
+        assert!(tensor.transpose_axis(-4, 0).is_err());
+        assert!(tensor.transpose_axis(3, 0).is_err());
+        assert!(tensor.permute_axes(&[-1, 2, 0]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn embedding() -> Res<()> {
+        let weight = Tensor::arange(0, 12, 1)?.view(&[3, 4])?;
+
+        let lookup = Tensor::embedding(&weight, &Tensor::new(&[0, 2, 1], &[3])?)?;
+        assert_eq!(lookup.sizes(), &[3, 4]);
+        assert_eq!(lookup.data(), vec![0, 1, 2, 3, 8, 9, 10, 11, 4, 5, 6, 7]);
+
+        let batched = Tensor::embedding(&weight, &Tensor::new(&[2, 2, 0, 1], &[2, 2])?)?;
+        assert_eq!(batched.sizes(), &[2, 2, 4]);
+        assert_eq!(
+            batched.single_slice(0, 1)?.data(),
+            vec![0, 1, 2, 3, 4, 5, 6, 7]
+        );
+
+        let transposed = Tensor::embedding(&weight.transpose(0, 1)?, &Tensor::new(&[3], &[1])?)?;
+        assert_eq!(transposed.data(), vec![3, 7, 11]);
+
+        assert!(Tensor::embedding(&weight, &Tensor::new(&[3], &[1])?).is_err());
+        assert!(Tensor::embedding(&weight.flatten()?, &Tensor::new(&[0], &[1])?).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_shapes_are_errors() -> Res<()> {
+        let tensor = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+
+        assert!(tensor.view(&[7]).is_err());
+        assert!(tensor.reshape(&[4, 2]).is_err());
+        assert!(tensor.transpose(0, 1)?.view(&[6]).is_err());
+        assert!(tensor.permute(&[0, 0]).is_err());
+        assert!(tensor.permute(&[0]).is_err());
+        assert!(tensor.permute(&[0, 2]).is_err());
+        assert!(tensor.expand(&[2, 4]).is_err());
+        assert!(tensor.expand(&[3]).is_err());
+        assert!(tensor.slice(&[(0, 3)]).is_err());
+        assert!(tensor.slice(&[(2, 1)]).is_err());
+        assert!(tensor.index(&[2, 0]).is_err());
+        assert!(tensor.index(&[0, 0, 0]).is_err());
+        assert_eq!(tensor.index_dims(&[1], &[2])?, 2);
+        assert!(tensor.index_dims(&[2], &[0]).is_err());
+        assert!(tensor.index_dims(&[0, 0], &[0, 1]).is_err());
+        assert!(tensor.index_dims(&[0, 1], &[0]).is_err());
+        assert!(tensor.index_map_dims(|x| x + 1, &[5], &[0]).is_err());
+        assert!(tensor.zip(&Tensor::ones(4)?, |a, b| a + b).is_err());
+        assert!(Tensor::new(&[1, 2, 3], &[2, 2]).is_err());
+
+        let scalar = Tensor::new(&[1], &[])?;
+        assert!(scalar.transpose(0, 0).is_err());
+        assert!(scalar.sum_dims(&[0], true).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn pad_sequences() -> Res<()> {
+        let a = Tensor::new(&[1, 2], &[2])?;
+        let b = Tensor::new(&[3, 4, 5], &[3])?;
+        let c = Tensor::new(&[6, 7, 8, 9, 10], &[5])?;
+
+        let (batch, lengths) = pad_sequence(&[&a, &b, &c], 0, true)?;
+        assert_eq!(batch.sizes(), &[3, 5]);
+        assert_eq!(
+            batch.data(),
+            vec![1, 2, 0, 0, 0, 3, 4, 5, 0, 0, 6, 7, 8, 9, 10]
+        );
+        assert_eq!(lengths.data(), vec![2, 3, 5]);
+
+        let (time_first, _) = pad_sequence(&[&a, &b, &c], -1, false)?;
+        assert_eq!(time_first.sizes(), &[5, 3]);
+        assert_eq!(
+            time_first.data(),
+            vec![1, 3, 6, 2, 4, 7, -1, 5, 8, -1, -1, 9, -1, -1, 10]
+        );
+
+        let features = Tensor::new(&[1, 2, 3, 4], &[2, 2])?;
+        let longer = Tensor::new(&[5, 6, 7, 8, 9, 10], &[3, 2])?;
+        let (padded, _) = pad_sequence(&[&features, &longer], 0, true)?;
+        assert_eq!(padded.sizes(), &[2, 3, 2]);
+        assert_eq!(padded.single_slice(0, 0)?.data(), vec![1, 2, 3, 4, 0, 0]);
+
+        assert!(pad_sequence::<i32>(&[], 0, true).is_err());
+        assert!(pad_sequence(&[&a, &features], 0, true).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn matchable_errors() -> Res<()> {
+        use crate::errors::{InvalidDataLengthError, ReshapeError, VenumError};
+
+        match Tensor::new(&[1, 2, 3], &[2, 2]).map_err(VenumError::from) {
+            Err(VenumError::InvalidDataLength(InvalidDataLengthError {
+                data_length,
+                tensor_size,
+            })) => assert_eq!((data_length, tensor_size), (3, 4)),
+            other => panic!("expected InvalidDataLength, got {other:?}"),
+        }
+
+        match Tensor::new(&[1, 2, 3, 4, 5, 6], &[6])?.view(&[7]) {
+            Err(VenumError::InvalidReshape(ReshapeError {
+                current_shape,
+                new_shape,
+            })) => assert_eq!((current_shape, new_shape), (vec![6], vec![7])),
+            other => panic!("expected InvalidReshape, got {other:?}"),
+        }
+
+        let error = Tensor::<i32>::ones(6)?.zip(&Tensor::ones(4)?, |l, r| l + r);
+        assert!(matches!(error, Err(VenumError::NonBroadcastable(_))));
+        assert!(matches!(
+            Tensor::<i32>::ones(6)?.index_map(|elem| elem + 1, &[6]),
+            Err(VenumError::OutOfBounds(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sequence_masks() -> Res<()> {
+        let lengths = Tensor::new(&[2, 3, 5], &[3])?;
+
+        let mask = sequence_mask(&lengths, 5)?;
+        assert_eq!(mask.sizes(), &[3, 5]);
+        assert_eq!(
+            mask.data(),
+            vec![
+                true, true, false, false, false, //
+                true, true, true, false, false, //
+                true, true, true, true, true, //
+            ]
+        );
+
+        let a = Tensor::new(&[1, 2], &[2])?;
+        let b = Tensor::new(&[3, 4, 5], &[3])?;
+        let (batch, lengths) = pad_sequence(&[&a, &b], 0, true)?;
+        let mask = sequence_mask(&lengths, batch.sizes()[1])?;
+        assert!(mask.equal(&batch.unary_map(|elem| elem != 0)?));
+
+        assert_eq!(sequence_mask(&lengths, 2)?.data(), vec![true; 4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn batched_matmul() -> Res<()> {
+        let lhs = Tensor::arange(0, 96, 1)?.view(&[8, 1, 4, 3])?;
+        let rhs = Tensor::arange(0, 90, 1)?.view(&[1, 6, 3, 5])?;
+
+        let product = lhs.matmul(&rhs)?;
+        assert_eq!(product.sizes(), &[8, 6, 4, 5]);
+
+        for b in 0..8 {
+            for h in 0..6 {
+                let expected = lhs
+                    .select(0, b)?
+                    .select(0, 0)?
+                    .matmul(&rhs.select(0, 0)?.select(0, h)?)?;
+                let actual = product.select(0, b)?.select(0, h)?;
+                assert!(actual.equal(&expected));
+            }
+        }
+
+        // Non-contiguous operands: [2, 3, 4] viewed as transposed [2, 4, 3].
+        let transposed = Tensor::arange(0, 24, 1)?
+            .view(&[2, 3, 4])?
+            .transpose(1, 2)?;
+        let batched = transposed.unsqueeze_dim(1)?.matmul(&rhs.select(0, 0)?)?;
+        assert_eq!(batched.sizes(), &[2, 6, 4, 5]);
+        for b in 0..2 {
+            for h in 0..6 {
+                let expected = transposed
+                    .select(0, b)?
+                    .to_contiguous()?
+                    .matmul(&rhs.select(0, 0)?.select(0, h)?)?;
+                assert!(batched.select(0, b)?.select(0, h)?.equal(&expected));
+            }
+        }
+
+        assert!(lhs.matmul(&rhs.view(&[1, 6, 5, 3])?).is_err());
+        assert!(lhs
+            .matmul(&Tensor::arange(0, 45, 1)?.view(&[3, 1, 3, 5])?)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn gather_offsets() -> Res<()> {
+        let tensor = Tensor::arange(1, 13, 1)?.view(&[4, 3])?;
+
+        // Shift column 0 down by one, leave column 1, and shift column 2 up by two.
+        let offsets = Tensor::new(&[-1, 0, 2], &[1, 3])?;
+
+        let wrapped = tensor.gather_offsets(&offsets, 0, BorderMode::Wrap)?;
+        assert_eq!(wrapped.data(), vec![10, 2, 9, 1, 5, 12, 4, 8, 3, 7, 11, 6]);
+
+        let clamped = tensor.gather_offsets(&offsets, 0, BorderMode::Clamp)?;
+        assert_eq!(clamped.data(), vec![1, 2, 9, 1, 5, 12, 4, 8, 12, 7, 11, 12]);
+
+        let zeroed = tensor.gather_offsets(&offsets, 0, BorderMode::Zero)?;
+        assert_eq!(zeroed.data(), vec![0, 2, 9, 1, 5, 12, 4, 8, 0, 7, 11, 0]);
+
+        let rolled = tensor.gather_offsets(&Tensor::new(&[1], &[1])?, 1, BorderMode::Wrap)?;
+        assert_eq!(rolled.data(), vec![2, 3, 1, 5, 6, 4, 8, 9, 7, 11, 12, 10]);
+
+        assert!(tensor
+            .gather_offsets(&Tensor::new(&[0, 0], &[2])?, 0, BorderMode::Wrap)
+            .is_err());
+        assert!(tensor
+            .gather_offsets(&offsets, 2, BorderMode::Wrap)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn conv2d_options() -> Res<()> {
+        use crate::conv::{conv2d, Conv2dOptions, Mode};
+
+        let input = Tensor::arange(0, 25, 1)?.view(&[5, 5])?;
+        let kernel = Tensor::new(&[1; 9], &[3, 3])?;
+
+        let options = Conv2dOptions {
+            stride: (2, 2),
+            padding: (1, 1),
+            ..Default::default()
+        };
+        let strided = conv2d(&input, &kernel, &options)?;
+        assert_eq!(strided.sizes(), &[3, 3]);
+        assert_eq!(strided.data(), vec![12, 27, 24, 63, 108, 81, 72, 117, 84]);
+
+        let dilated = conv2d(
+            &input,
+            &kernel,
+            &Conv2dOptions {
+                dilation: (2, 2),
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(dilated.sizes(), &[1, 1]);
+        assert_eq!(dilated.data(), vec![108]);
+
+        let weights = Tensor::new(&[1, -1, 2, 0, 3, 1, -2, 1, 1], &[3, 3])?;
+        let batched = Tensor::stack(&[&input, &input.transpose(0, 1)?], 0)?;
+        let plain = conv2d(&batched, &weights, &Conv2dOptions::default())?;
+        assert_eq!(plain.sizes(), &[2, 3, 3]);
+        assert!(plain.equal(&batched.correlate_2d(&weights, &[1, 1], Mode::Valid)?));
+
+        let zero_stride = Conv2dOptions {
+            stride: (0, 1),
+            ..Default::default()
+        };
+        assert!(conv2d(&input, &kernel, &zero_stride).is_err());
+        let too_dilated = Conv2dOptions {
+            dilation: (3, 1),
+            ..Default::default()
+        };
+        assert!(conv2d(&input, &kernel, &too_dilated).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn as_mut_slice() -> Res<()> {
+        let original = Tensor::arange(0, 12, 1)?.view(&[3, 4])?;
+
+        let mut row = original.narrow(0, 1, 1)?;
+        for elem in row.as_mut_slice()? {
+            *elem *= 10;
+        }
+        assert_eq!(row.data(), vec![40, 50, 60, 70]);
+        assert_eq!(original.data(), (0..12).collect::<Vec<i32>>());
+
+        let mut tensor = original.to_contiguous()?;
+        tensor.as_mut_slice()?.reverse();
+        assert_eq!(tensor.data(), (0..12).rev().collect::<Vec<i32>>());
+
+        assert!(original.transpose(0, 1)?.as_mut_slice().is_err());
+        assert!(original.narrow(1, 0, 2)?.as_mut_slice().is_err());
+        assert!(original.flip_all()?.as_mut_slice().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn pooling() -> Res<()> {
+        let tensor = Tensor::<f64>::new(
+            &[
+                1.0, 3.0, 2.0, 0.0, //
+                4.0, 2.0, 1.0, 5.0, //
+                -1.0, 0.0, 6.0, 2.0, //
+                3.0, 1.0, 2.0, 2.0, //
+            ],
+            &[1, 1, 4, 4],
+        )?;
+
+        let max = tensor.maxpool2d((2, 2), (2, 2))?;
+        assert_eq!(max.sizes(), &[1, 1, 2, 2]);
+        assert_eq!(max.data(), vec![4.0, 5.0, 3.0, 6.0]);
+
+        let avg = tensor.avgpool2d((2, 2), (2, 2))?;
+        assert_eq!(avg.sizes(), &[1, 1, 2, 2]);
+        assert_eq!(avg.data(), vec![2.5, 2.0, 0.75, 3.0]);
+
+        // The last row and column do not fit a full window and are dropped.
+        let uneven = tensor.narrow(2, 0, 3)?.maxpool2d((2, 2), (2, 2))?;
+        assert_eq!(uneven.sizes(), &[1, 1, 1, 2]);
+        assert_eq!(uneven.data(), vec![4.0, 5.0]);
+
+        let overlapping = tensor.maxpool2d((3, 3), (1, 1))?;
+        assert_eq!(overlapping.data(), vec![6.0, 6.0, 6.0, 6.0]);
+
+        assert!(tensor.maxpool2d((5, 2), (1, 1)).is_err());
+        assert!(tensor.avgpool2d((2, 2), (0, 2)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn ensure_contiguous() -> Res<()> {
+        let tensor = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+
+        let same = tensor.view_same().ensure_contiguous()?;
+        assert!(Arc::ptr_eq(&same.data, &tensor.data));
+
+        let row = tensor.narrow(0, 1, 1)?.ensure_contiguous()?;
+        assert!(Arc::ptr_eq(&row.data, &tensor.data));
+        assert_eq!(row.data(), vec![3, 4, 5]);
+
+        let transposed = tensor.transpose(0, 1)?.ensure_contiguous()?;
+        assert!(!Arc::ptr_eq(&transposed.data, &tensor.data));
+        assert!(transposed.is_contiguous());
+        assert_eq!(transposed.data(), vec![0, 3, 1, 4, 2, 5]);
+
+        let flipped = tensor.flip_all()?.ensure_contiguous()?;
+        assert!(!Arc::ptr_eq(&flipped.data, &tensor.data));
+        assert_eq!(flipped.data.as_slice(), &[5, 4, 3, 2, 1, 0]);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_maps() -> Res<()> {
+        let tensor = Tensor::<f64>::arange(0.0, 1_000_000.0, 1.0)?.view(&[1000, 1000])?;
+        let values = tensor.data();
+        let other = tensor.unary_map(|elem| elem.sqrt())?;
+
+        let unary = tensor.unary_map(|elem| elem * 0.5 - 3.0)?;
+        let expected = values
+            .iter()
+            .map(|elem| elem * 0.5 - 3.0)
+            .collect::<Vec<_>>();
+        assert_eq!(unary.data(), expected);
+
+        let binary = tensor.binary_map(7.0, |lhs, rhs| lhs % rhs)?;
+        let expected = values.iter().map(|elem| elem % 7.0).collect::<Vec<_>>();
+        assert_eq!(binary.data(), expected);
+
+        let zipped = tensor.zip(&other, |lhs, rhs| lhs - rhs)?;
+        let expected = values
+            .iter()
+            .map(|elem| elem - elem.sqrt())
+            .collect::<Vec<_>>();
+        assert_eq!(zipped.data(), expected);
+
+        let transposed = tensor.transpose(0, 1)?;
+        let product = transposed.zip(&other, |lhs, rhs| lhs * rhs)?;
+        assert_eq!(product.idx(&[2, 5]), 5002.0 * 2005.0_f64.sqrt());
+
+        let flipped = tensor.flip_all()?;
+        let flipped_unary = flipped.unary_map(|elem| elem + 1.0)?;
+        assert_eq!(flipped_unary.idx(&[0, 0]), 1_000_000.0);
+        assert_eq!(flipped_unary.idx(&[999, 999]), 1.0);
+
+        let rows = tensor.sum_dims(&[1], false)?;
+        let expected = (0..1000)
+            .map(|row| values[row * 1000..(row + 1) * 1000].iter().sum::<f64>())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.sizes(), &[1000]);
+        assert_eq!(rows.data(), expected);
+
+        let total = tensor.par_reduce(0.0, |elem| elem, |lhs, rhs| lhs + rhs);
+        assert_eq!(total, 499_999_500_000.0);
+        let flipped_total = flipped.par_reduce(0.0, |elem| elem, |lhs, rhs| lhs + rhs);
+        assert_eq!(flipped_total, total);
+
+        Ok(())
+    }
+
+    #[test]
+    fn send_sync() -> Res<()> {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Tensor<f32>>();
+        assert_send_sync::<Tensor<usize>>();
+        assert_send_sync::<Tensor<bool>>();
+        assert_send_sync::<Tensor<Offset>>();
+        assert_send_sync::<crate::errors::VenumError>();
+
+        let tensor = Tensor::arange(0, 12, 1)?.view(&[3, 4])?;
+        let sums = std::thread::scope(|scope| {
+            let handles = (0..3)
+                .map(|row| {
+                    let view = tensor.select(0, row);
+                    scope.spawn(move || view.map(|view| view.data().iter().sum::<i32>()))
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("thread panicked"))
+                .collect::<Res<Vec<i32>>>()
+        })?;
+        assert_eq!(sums, vec![6, 22, 38]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn display() -> Res<()> {
+        let tensor = Tensor::new(&[1, 2, 3, 4, 5, 6], &[2, 3])?;
+
+        assert_eq!(
+            tensor.to_string(),
+            "┌───┬───┬───┐\n\
+             │ 1 │ 2 │ 3 │\n\
+             ├───┼───┼───┤\n\
+             │ 4 │ 5 │ 6 │\n\
+             └───┴───┴───┘\n\
+             Tensor { dtype: \"i32\", dims: 2, elems: 6, shape: [2, 3] }\n"
+        );
+        assert_eq!(
+            tensor.flip(&[1])?.to_string(),
+            "┌───┬───┬───┐\n\
+             │ 3 │ 2 │ 1 │\n\
+             ├───┼───┼───┤\n\
+             │ 6 │ 5 │ 4 │\n\
+             └───┴───┴───┘\n\
+             Tensor { dtype: \"i32\", dims: 2, elems: 6, shape: [2, 3] }\n"
+        );
+
+        let large = Tensor::arange(0, 2000, 1)?.view(&[40, 50])?.to_string();
+        let first_row = large.lines().nth(1).unwrap_or_default();
+        assert_eq!(
+            first_row,
+            "│ 0    │ 1    │ 2    │ ... │ 47   │ 48   │ 49   │"
+        );
+        assert_eq!(large.lines().filter(|line| line.contains("...")).count(), 7);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_reduce() -> Res<()> {
+        let tensor = Tensor::<i64>::arange(-50_000, 50_000, 1)?.view(&[400, 250])?;
+
+        let sequential = tensor.data().iter().fold(0, |acc, &elem| acc + elem * elem);
+        let squares = tensor.par_reduce(0, |elem| elem * elem, |lhs, rhs| lhs + rhs);
+        assert_eq!(squares, sequential);
+
+        let transposed = tensor.transpose(0, 1)?;
+        let first = transposed.par_reduce(None, Some, |lhs, rhs| lhs.or(rhs));
+        assert_eq!(first, Some(-50_000));
+
+        let (min, max) = tensor.flip_all()?.par_reduce(
+            (i64::MAX, i64::MIN),
+            |elem| (elem, elem),
+            |lhs, rhs| (lhs.0.min(rhs.0), lhs.1.max(rhs.1)),
+        );
+        assert_eq!((min, max), (-50_000, 49_999));
+
+        Ok(())
+    }
+
+    #[test]
+    fn debug_layout() -> Res<()> {
+        let tensor = Tensor::arange(0, 12, 1)?.view(&[3, 4])?;
+
+        assert_eq!(
+            format!("{:?}", tensor),
+            "Tensor { sizes: [3, 4], strides: [Positive(4), Positive(1)], offset: 0, numel: 12, data_len: 12 }"
+        );
+
+        let sliced = tensor.narrow(0, 1, 2)?.flip(&[1])?;
+        let debug = format!("{:?}", sliced);
+        assert!(debug.contains("strides: [Positive(4), Negative(1)]"));
+        assert!(debug.contains("offset: 4"));
+        assert!(debug.contains("numel: 8, data_len: 12"));
+
+        assert!(!format!("{:?}", tensor).contains("dtype"));
+        assert!(tensor
+            .to_string()
+            .ends_with("Tensor { dtype: \"i32\", dims: 2, elems: 12, shape: [3, 4] }\n"));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_iter_dim() -> Res<()> {
+        use rayon::prelude::*;
+
+        let tensor = Tensor::arange(0, 24, 1)?.view(&[4, 6])?;
+
+        let row_sums = tensor
+            .par_iter_dim(0)?
+            .map(|row| row.sum())
+            .collect::<Res<Vec<i32>>>()?;
+        let sequential = (0..4)
+            .map(|row| tensor.select(0, row)?.sum())
+            .collect::<Res<Vec<i32>>>()?;
+        assert_eq!(row_sums, sequential);
+
+        let columns = tensor
+            .par_iter_dim(1)?
+            .map(|column| column.data())
+            .collect::<Vec<Vec<i32>>>();
+        assert_eq!(columns.len(), 6);
+        assert_eq!(columns[2], vec![2, 8, 14, 20]);
+
+        assert!(tensor.par_iter_dim(2).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn matmul_nt() -> Res<()> {
+        let lhs = Tensor::arange(0, 12, 1)?.view(&[3, 4])?;
+        let rhs = Tensor::arange(-10, 10, 1)?.view(&[4, 5])?;
+
+        let general = lhs.matmul(&rhs)?;
+        let rhs_t = rhs.transpose(0, 1)?;
+        assert!(lhs.matmul_nt(&rhs_t)?.equal(&general));
+        assert!(lhs.matmul_nt(&rhs_t.to_contiguous()?)?.equal(&general));
+
+        let flipped = lhs.flip_all()?;
+        assert!(flipped.matmul_nt(&rhs_t)?.equal(&flipped.matmul(&rhs)?));
+
+        let empty = Tensor::<i32>::new(&[], &[3, 0])?;
+        assert_eq!(
+            empty.matmul_nt(&Tensor::new(&[], &[2, 0])?)?.data(),
+            vec![0; 6]
+        );
+
+        assert!(lhs.matmul_nt(&rhs).is_err());
+        assert!(lhs.matmul_nt(&rhs.view(&[20])?).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn cast() -> Res<()> {
+        let tensor = Tensor::<i32>::arange(0, 6, 1)?.view(&[2, 3])?;
+
+        let floats = tensor.to_f64()?;
+        assert_eq!(floats.sizes(), &[2, 3]);
+        assert_eq!(floats.data(), vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(floats.mean()?, 2.5);
+
+        let transposed = tensor.transpose(0, 1)?.to_f64()?;
+        assert_eq!(transposed.sizes(), &[3, 2]);
+        assert_eq!(transposed.data(), vec![0.0, 3.0, 1.0, 4.0, 2.0, 5.0]);
+
+        let flipped = tensor.flip(&[1])?.cast::<i64>()?;
+        assert_eq!(flipped.data(), vec![2, 1, 0, 5, 4, 3]);
+
+        let small = Tensor::<u8>::new(&[1, 255], &[2])?;
+        assert_eq!(small.to_f32()?.data(), vec![1.0, 255.0]);
+        assert_eq!(small.to_i64()?.data(), vec![1, 255]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cumsum_cumprod() -> Res<()> {
+        let tensor = Tensor::new(&[1, 2, 3, 4, 5, 6], &[2, 3])?;
+
+        let rows = tensor.cumsum(1, false)?;
+        assert_eq!(rows.sizes(), &[2, 3]);
+        assert_eq!(rows.data(), vec![1, 3, 6, 4, 9, 15]);
+
+        let transposed = tensor.transpose(0, 1)?.cumsum(0, false)?;
+        assert_eq!(transposed.sizes(), &[3, 2]);
+        assert_eq!(transposed.data(), vec![1, 4, 3, 9, 6, 15]);
+
+        assert_eq!(tensor.cumsum(0, false)?.data(), vec![1, 2, 3, 5, 7, 9]);
+        assert_eq!(tensor.cumprod(1, false)?.data(), vec![1, 2, 6, 4, 20, 120]);
+        assert_eq!(
+            tensor.flip(&[1])?.cumprod(1, false)?.data(),
+            vec![3, 6, 6, 6, 30, 120]
+        );
+
+        let column = tensor.narrow(1, 2, 1)?;
+        let unchanged = column.cumsum(1, true)?;
+        assert!(unchanged.equal(&column));
+        assert!(Arc::ptr_eq(&unchanged.data, &tensor.data));
+
+        assert!(tensor.cumsum(2, false).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn ger() -> Res<()> {
+        let mut tensor = Tensor::<i32>::zeroes(6)?.view(&[2, 3])?;
+        let (x1, y1) = (Tensor::new(&[1, 2], &[2])?, Tensor::new(&[3, 4, 5], &[3])?);
+        let (x2, y2) = (Tensor::new(&[-1, 1], &[2])?, Tensor::new(&[1, 0, 2], &[3])?);
+
+        tensor.ger(2, &x1, &y1)?;
+        tensor.ger(3, &x2, &y2)?;
+
+        let dense =
+            (x1.outer_map(&y1, |a, b| 2 * a * b)? + x2.outer_map(&y2, |a, b| 3 * a * b)?)?;
+        assert!(tensor.equal(&dense));
+        assert_eq!(tensor.data(), vec![3, 8, 4, 15, 16, 26]);
+
+        let original = Tensor::new(&[1, 1, 1, 1], &[2, 2])?;
+        let mut transposed = original.transpose(0, 1)?;
+        transposed.ger(
+            1,
+            &Tensor::new(&[1, 0], &[2])?,
+            &Tensor::new(&[0, 5], &[2])?,
+        )?;
+        assert_eq!(transposed.data(), vec![1, 6, 1, 1]);
+        assert_eq!(original.data(), vec![1, 1, 1, 1]);
+
+        let row = Tensor::<i32>::zeroes(3)?.view(&[1, 3])?;
+        let mut expanded = row.expand(&[2, 3])?;
+        expanded.ger(1, &Tensor::ones(2)?, &Tensor::ones(3)?)?;
+        assert_eq!(expanded.data(), vec![1; 6]);
+        assert_eq!(row.data(), vec![0; 3]);
+
+        assert!(tensor.ger(1, &y1, &x1).is_err());
+        assert!(tensor.ger(1, &tensor.view_same(), &y1).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn sort() -> Res<()> {
+        let tensor = Tensor::new(&[3, 1, 2, 1, 3, 1, 2, 2, 0], &[3, 3])?;
+
+        assert_eq!(
+            tensor.sort(1, false, true)?.data(),
+            vec![1, 2, 3, 1, 1, 3, 0, 2, 2]
+        );
+        assert_eq!(
+            tensor.argsort(1, false, true)?.data(),
+            vec![1, 2, 0, 0, 2, 1, 2, 0, 1]
+        );
+        assert_eq!(
+            tensor.sort(0, true, true)?.data(),
+            vec![3, 3, 2, 2, 2, 1, 1, 1, 0]
+        );
+        assert_eq!(
+            tensor.argsort(0, true, true)?.data(),
+            vec![0, 1, 0, 2, 2, 1, 1, 0, 2]
+        );
+
+        let transposed = tensor.transpose(0, 1)?;
+        assert!(transposed
+            .sort(0, false, true)?
+            .equal(&tensor.sort(1, false, true)?.transpose(0, 1)?));
+        assert!(tensor.sort(1, false, true)?.is_contiguous());
+
+        let floats = Tensor::new(&[2.0, f64::NAN, -1.0, 5.0], &[4])?;
+        let ascending = floats.sort(0, false, true)?.data();
+        assert_eq!(ascending[..3], [-1.0, 2.0, 5.0]);
+        assert!(ascending[3].is_nan());
+        assert_eq!(floats.argsort(0, true, true)?.data(), vec![3, 0, 2, 1]);
+
+        assert!(tensor.sort(2, false, true).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn cumsum_reverse() -> Res<()> {
+        let vector = Tensor::new(&[1, 2, 3], &[3])?;
+        assert_eq!(vector.cumsum(0, true)?.data(), vec![6, 5, 3]);
+        assert_eq!(vector.cumprod(0, true)?.data(), vec![6, 6, 3]);
+
+        let tensor = Tensor::new(&[1, 2, 3, 4, 5, 6], &[2, 3])?;
+        assert_eq!(tensor.cumsum(1, true)?.data(), vec![6, 5, 3, 15, 11, 6]);
+        assert_eq!(tensor.cumsum(0, true)?.data(), vec![5, 7, 9, 4, 5, 6]);
+
+        let flipped = tensor.flip(&[1])?.cumsum(1, false)?.flip(&[1])?;
+        assert!(tensor.cumsum(1, true)?.equal(&flipped));
+
+        let transposed = tensor.transpose(0, 1)?;
+        assert_eq!(transposed.cumsum(0, true)?.data(), vec![6, 15, 5, 11, 3, 6]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn masked_select_fill() -> Res<()> {
+        let tensor = Tensor::new(&[5, 1, 4, 2, 6, 3], &[2, 3])?;
+        let mask = tensor.unary_map(|elem| elem > 3)?;
+
+        let selected = tensor.masked_select(&mask)?;
+        assert_eq!(selected.sizes(), &[3]);
+        assert_eq!(selected.data(), vec![5, 4, 6]);
+        assert_eq!(tensor.masked_fill(&mask, 0)?.data(), vec![0, 1, 0, 2, 0, 3]);
+
+        let transposed = tensor.transpose(0, 1)?;
+        let transposed_mask = mask.transpose(0, 1)?;
+        assert_eq!(
+            transposed.masked_select(&transposed_mask)?.data(),
+            vec![5, 6, 4]
+        );
+
+        let columns = Tensor::new(&[true, false, true], &[3])?;
+        assert_eq!(tensor.masked_select(&columns)?.data(), vec![5, 4, 2, 3]);
+        assert_eq!(
+            tensor.masked_fill(&columns, -1)?.data(),
+            vec![-1, 1, -1, -1, 6, -1]
+        );
+
+        assert!(tensor
+            .masked_select(&Tensor::new(&[true, false], &[2])?)
+            .is_err());
+        assert!(tensor
+            .masked_fill(&mask.unsqueeze(3)?.expand(&[2, 2, 3])?, 0)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn pad_crop() -> Res<()> {
+        let vector = Tensor::new(&[1, 2, 3, 4, 5], &[5])?;
+        assert_eq!(vector.pad_crop(&[0], &[(-1, -1)], 0)?.data(), vec![2, 3, 4]);
+        assert_eq!(
+            vector.pad_crop(&[0], &[(-2, 1)], 9)?.data(),
+            vec![3, 4, 5, 9]
+        );
+        assert!(vector.pad_crop(&[0], &[(1, 2)], 0)?.equal(&vector.pad_dims(
+            0,
+            &[0],
+            &[(1, 2)]
+        )?));
+        assert_eq!(vector.pad_crop(&[0], &[(-3, -2)], 0)?.sizes(), &[0]);
+
+        let matrix = Tensor::new(&[1, 2, 3, 4, 5, 6], &[2, 3])?;
+        let result = matrix.pad_crop(&[1, 0], &[(-1, 0), (1, 0)], 0)?;
+        assert_eq!(result.sizes(), &[3, 2]);
+        assert_eq!(result.data(), vec![0, 0, 2, 3, 5, 6]);
+        assert!(result.is_contiguous());
+
+        assert!(vector.pad_crop(&[0], &[(-3, -3)], 0).is_err());
+        assert!(vector.pad_crop(&[0], &[], 0).is_err());
+        assert!(vector.pad_crop(&[1], &[(-1, 0)], 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn comparisons() -> Res<()> {
+        let tensor = Tensor::new(&[5, 1, 4, 2, 6, 3], &[2, 3])?;
+
+        assert_eq!(
+            tensor.gt(3)?.data(),
+            vec![true, false, true, false, true, false]
+        );
+        assert_eq!(
+            tensor.ge(3)?.data(),
+            vec![true, false, true, false, true, true]
+        );
+        assert_eq!(
+            tensor.lt(3)?.data(),
+            vec![false, true, false, true, false, false]
+        );
+        assert_eq!(
+            tensor.le(2)?.data(),
+            vec![false, true, false, true, false, false]
+        );
+        assert_eq!(
+            tensor.eq_elem(4)?.data(),
+            vec![false, false, true, false, false, false]
+        );
+        assert_eq!(
+            tensor.ne_elem(4)?.data(),
+            vec![true, true, false, true, true, true]
+        );
+
+        assert_eq!(tensor.masked_select(&tensor.gt(3)?)?.data(), vec![5, 4, 6]);
+        assert_eq!(
+            tensor.masked_fill(&tensor.gt(3)?, 3)?.data(),
+            vec![3, 1, 3, 2, 3, 3]
+        );
+
+        let row = Tensor::new(&[4, 2, 4], &[3])?;
+        assert_eq!(
+            tensor.gt_tensor(&row)?.data(),
+            vec![true, false, false, false, true, false]
+        );
+        assert_eq!(
+            tensor.eq_elem_tensor(&row)?.data(),
+            vec![false, false, true, false, false, false]
+        );
+        assert_eq!(tensor.le_tensor(&row)?.sizes(), &[2, 3]);
+        assert!(tensor.lt_tensor(&Tensor::new(&[1, 2], &[2])?).is_err());
+
+        let floats = Tensor::new(&[f64::NAN, 1.0], &[2])?;
+        assert_eq!(floats.eq_elem_tensor(&floats)?.data(), vec![false, true]);
+        assert_eq!(floats.ne_elem(f64::NAN)?.data(), vec![true, true]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn roll() -> Res<()> {
+        let tensor = Tensor::arange(1, 7, 1)?;
+
+        assert_eq!(tensor.roll(2, 0)?.data(), vec![5, 6, 1, 2, 3, 4]);
+        assert_eq!(tensor.roll(-1, 0)?.data(), vec![2, 3, 4, 5, 6, 1]);
+        assert!(tensor.roll(6, 0)?.equal(&tensor));
+        assert!(tensor.roll(-13, 0)?.equal(&tensor.roll(-1, 0)?));
+
+        let matrix = tensor.view(&[2, 3])?;
+        assert_eq!(matrix.roll(1, 1)?.data(), vec![3, 1, 2, 6, 4, 5]);
+
+        let rolled = matrix.transpose(0, 1)?.roll(1, 0)?;
+        assert_eq!(rolled.data(), vec![3, 6, 1, 4, 2, 5]);
+        assert!(rolled.is_contiguous());
+        assert_eq!(rolled.view(&[6])?.data(), vec![3, 6, 1, 4, 2, 5]);
+
+        let flipped = matrix.flip(&[0, 1])?.roll(0, 1)?;
+        assert!(flipped.is_contiguous());
+        assert_eq!(flipped.data(), vec![6, 5, 4, 3, 2, 1]);
+
+        assert!(matrix.roll(1, 2).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn sort_stable() -> Res<()> {
+        let keys = Tensor::new(&[2, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2], &[2, 6])?;
+
+        let ascending = keys.argsort(1, false, true)?;
+        assert_eq!(ascending.data(), vec![3, 1, 4, 0, 2, 5, 0, 3, 1, 4, 2, 5]);
+
+        let descending = keys.argsort(1, true, true)?;
+        assert_eq!(descending.data(), vec![0, 2, 5, 1, 4, 3, 2, 5, 1, 4, 0, 3]);
+
+        let gathered = keys.take_along_dim(&ascending, 1)?;
+        assert!(gathered.equal(&keys.sort(1, false, true)?));
+
+        let unstable = keys.sort(1, false, false)?;
+        assert!(unstable.equal(&keys.sort(1, false, true)?));
+        assert!(keys
+            .take_along_dim(&keys.argsort(1, true, false)?, 1)?
+            .equal(&keys.sort(1, true, true)?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn gather_scatter() -> Res<()> {
+        let tensor = Tensor::new(&[10, 11, 12, 13, 20, 21, 22, 23], &[2, 4])?;
+        let index = Tensor::new(&[3, 0, 1, 1], &[2, 2])?;
+
+        let gathered = tensor.gather(1, &index)?;
+        assert_eq!(gathered.sizes(), &[2, 2]);
+        assert_eq!(gathered.data(), vec![13, 10, 21, 21]);
+
+        let rows = tensor.gather(0, &Tensor::new(&[1, 0, 0, 1], &[1, 4])?)?;
+        assert_eq!(rows.data(), vec![20, 11, 12, 23]);
+
+        let zeroes = Tensor::<i32>::zeroes(8)?.view(&[2, 4])?;
+        let scattered = zeroes.scatter(1, &index, &gathered)?;
+        assert_eq!(scattered.data(), vec![10, 0, 0, 13, 0, 21, 0, 0]);
+        assert!(scattered.gather(1, &index)?.equal(&gathered));
+
+        assert!(tensor
+            .gather(1, &Tensor::new(&[4, 0, 1, 1], &[2, 2])?)
+            .is_err());
+        assert!(tensor.gather(1, &Tensor::new(&[0, 1], &[2])?).is_err());
+        assert!(zeroes
+            .scatter(1, &Tensor::new(&[0, 5, 1, 1], &[2, 2])?, &gathered)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn take() -> Res<()> {
+        let tensor = Tensor::new(&[10, 11, 12, 13, 14, 15], &[2, 3])?;
+        let indices = Tensor::new(&[0, 5, 7, 13], &[2, 2])?;
+
+        let wrapped = tensor.take(&indices, IndexMode::Wrap)?;
+        assert_eq!(wrapped.sizes(), &[2, 2]);
+        assert_eq!(wrapped.data(), vec![10, 15, 11, 11]);
+        assert_eq!(
+            tensor.take(&indices, IndexMode::Clip)?.data(),
+            vec![10, 15, 15, 15]
+        );
+        assert!(tensor.take(&indices, IndexMode::Raise).is_err());
+
+        let in_range = Tensor::new(&[4, 1], &[2])?;
+        assert_eq!(
+            tensor.take(&in_range, IndexMode::Raise)?.data(),
+            vec![14, 11]
+        );
+        assert_eq!(
+            tensor
+                .transpose(0, 1)?
+                .take(&in_range, IndexMode::Raise)?
+                .data(),
+            vec![12, 13]
+        );
+
+        let empty = Tensor::<i32>::new(&[], &[0])?;
+        assert!(empty.take(&in_range, IndexMode::Wrap).is_err());
+        assert!(empty.take(&in_range, IndexMode::Clip).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn index_select() -> Res<()> {
+        let tensor = Tensor::arange(0, 9, 1)?.view(&[3, 3])?;
+
+        let rows = tensor.index_select(0, &[2, 0, 2])?;
+        assert_eq!(rows.sizes(), &[3, 3]);
+        assert_eq!(rows.data(), vec![6, 7, 8, 0, 1, 2, 6, 7, 8]);
+        assert!(rows.is_contiguous());
+
+        let columns = tensor.transpose(0, 1)?.index_select(1, &[1, 1])?;
+        assert_eq!(columns.sizes(), &[3, 2]);
+        assert_eq!(columns.data(), vec![3, 3, 4, 4, 5, 5]);
+
+        assert_eq!(tensor.index_select(1, &[])?.sizes(), &[3, 0]);
+        assert!(tensor.index_select(0, &[0, 3]).is_err());
+        assert!(tensor.index_select(2, &[0]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn ravel_into() -> Res<()> {
+        let tensor = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+        let buffer = tensor.data.as_ptr();
+        let raveled = tensor.ravel_into()?;
+        assert_eq!(raveled.sizes(), &[6]);
+        assert_eq!(raveled.data.as_ptr(), buffer);
+        assert_eq!(raveled.data(), vec![0, 1, 2, 3, 4, 5]);
+
+        let narrowed = Tensor::arange(0, 6, 1)?.view(&[3, 2])?.narrow(0, 1, 2)?;
+        let buffer = narrowed.data.as_ptr();
+        let raveled = narrowed.ravel_into()?;
+        assert_eq!(raveled.data.as_ptr(), buffer);
+        assert_eq!(*raveled.data, vec![2, 3, 4, 5]);
+
+        let flipped = Tensor::arange(0, 6, 1)?.view(&[2, 3])?.flip(&[0, 1])?;
+        let buffer = flipped.data.as_ptr();
+        let raveled = flipped.ravel_into()?;
+        assert_eq!(raveled.data.as_ptr(), buffer);
+        assert_eq!(*raveled.data, vec![5, 4, 3, 2, 1, 0]);
+
+        let shared = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+        let raveled = shared.flip(&[0, 1])?.ravel_into()?;
+        assert_eq!(raveled.data(), vec![5, 4, 3, 2, 1, 0]);
+        assert_eq!(shared.data(), vec![0, 1, 2, 3, 4, 5]);
+        assert!(!Arc::ptr_eq(&shared.ravel_into()?.data, &raveled.data));
+
+        let transposed = Tensor::arange(0, 6, 1)?.view(&[2, 3])?.transpose(0, 1)?;
+        let raveled = transposed.ravel_into()?;
+        assert!(raveled.is_contiguous());
+        assert_eq!(raveled.data(), vec![0, 3, 1, 4, 2, 5]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn where_() -> Res<()> {
+        let lhs = Tensor::new(&[1, -2, 3, -4, 5, -6], &[2, 3])?;
+        let rhs = Tensor::new(&[10, 20, 30, 40, 50, 60], &[2, 3])?;
+        let condition = Tensor::new(&[true, false, false, true, true, false], &[2, 3])?;
+
+        let expected = Tensor::new(&[1, 20, 30, -4, 5, 60], &[2, 3])?;
+        assert!(Tensor::where_(&condition, &lhs, &rhs)?.equal(&expected));
+
+        let relu = Tensor::where_(&lhs.gt(0)?, &lhs, &Tensor::scalar(0)?)?;
+        assert_eq!(relu.data(), vec![1, 0, 3, 0, 5, 0]);
+
+        let always = Tensor::scalar(true)?;
+        assert!(Tensor::where_(&always, &lhs, &rhs)?.equal(&lhs));
+        assert!(Tensor::where_(&Tensor::new(&[false], &[1])?, &lhs, &rhs)?.equal(&rhs));
+
+        let columns = Tensor::new(&[true, false], &[2, 1])?;
+        let mixed = Tensor::where_(&columns, &lhs.narrow(1, 0, 1)?, &rhs)?;
+        assert_eq!(mixed.sizes(), &[2, 3]);
+        assert_eq!(mixed.data(), vec![1, 1, 1, 40, 50, 60]);
+
+        assert!(Tensor::where_(&Tensor::new(&[true, false], &[2])?, &lhs, &rhs).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn zip_incompatible_shapes() -> Res<()> {
+        let lhs = Tensor::<i32>::ones(6)?.view(&[2, 3])?;
+        let rhs = Tensor::<i32>::ones(20)?.view(&[4, 5])?;
+
+        let error = lhs.zip(&rhs, |l, r| l + r).err().unwrap();
+        assert!(error.to_string().contains("[2, 3]"));
+        assert!(error.to_string().contains("[4, 5]"));
+
+        assert!((&lhs + &rhs).is_err());
+        assert!(lhs
+            .zip_with(&rhs.gt(0)?, |l, keep| keep.then_some(l))
+            .is_err());
+        assert!(lhs.zip3(&lhs, &rhs, |a, b, c| a + b + c).is_err());
+        assert!(Tensor::where_(&rhs.gt(0)?, &lhs, &lhs).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn all_any() -> Res<()> {
+        let tensor = Tensor::new(&[1, 5, 7, 2, 6, 8], &[2, 3])?;
+        let mask = tensor.gt(1)?;
+
+        assert!(!mask.all_true());
+        assert!(mask.any_true());
+        assert!(tensor.gt(0)?.all_true());
+        assert!(!tensor.gt(8)?.any_true());
+
+        assert_eq!(mask.all(&[1], true)?.data(), vec![false, true]);
+        assert_eq!(mask.any(&[1], true)?.data(), vec![true, true]);
+        assert_eq!(mask.all(&[0], true)?.data(), vec![false, true, true]);
+        assert_eq!(mask.all(&[0], false)?.sizes(), &[3]);
+        assert_eq!(mask.all(&[0], false)?.data(), vec![false, true, true]);
+
+        let everything = mask.all(&[], false)?;
+        assert_eq!(everything.sizes(), &[]);
+        assert_eq!(everything.data(), vec![false]);
+        assert_eq!(mask.all(&[], true)?.sizes(), &[]);
+        assert_eq!(mask.any(&[], true)?.sizes(), &[]);
+        assert_eq!(mask.any(&[], true)?.data(), vec![true]);
+
+        let transposed = mask.transpose(0, 1)?;
+        assert_eq!(transposed.all(&[0], true)?.data(), vec![false, true]);
+        assert!(!transposed.all_true());
+
+        let empty = Tensor::<bool>::new(&[], &[0])?;
+        assert!(empty.all_true());
+        assert!(!empty.any_true());
+
+        assert!(mask.any(&[2], true).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn var_std() -> Res<()> {
+        // Mean 5, squared deviations sum to 32.
+        let vector = Tensor::<f64>::new(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0], &[8])?;
+
+        assert_eq!(vector.var(&[], false, 0)?.data(), vec![4.0]);
+        assert_eq!(vector.std(&[], false, 0)?.data(), vec![2.0]);
+
+        let sample = vector.std(&[0], true, 1)?;
+        assert_eq!(sample.sizes(), &[1]);
+        assert!((sample.data()[0] - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+
+        let matrix = Tensor::new(&[1.0, 2.0, 3.0, 2.0, 4.0, 6.0], &[2, 3])?;
+        assert_eq!(matrix.var(&[1], true, 1)?.data(), vec![1.0, 4.0]);
+        assert_eq!(matrix.var(&[0], true, 0)?.data(), vec![0.25, 1.0, 2.25]);
+        assert_eq!(
+            matrix.transpose(0, 1)?.std(&[0], true, 1)?.data(),
+            vec![1.0, 2.0]
+        );
+
+        assert!(vector.var(&[0], true, 8).is_err());
+        assert!(matrix.std(&[1], true, 4).is_err());
+        assert!(matrix.var(&[2], true, 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn expand_incompatible_size() -> Res<()> {
+        let tensor = Tensor::arange(0, 6, 1)?.view(&[2, 3])?;
+
+        let error = tensor.expand(&[4, 3]).err().unwrap().to_string();
+        assert_eq!(
+            error,
+            "Size 2 cannot be expanded to size 4. To be expanded, size should be 1."
+        );
+
+        assert!(tensor.broadcast_to(&[4, 3]).is_err());
+        assert!(tensor.expand(&[2, 3, 1]).is_err());
+        assert_eq!(tensor.expand(&[2, 3])?.sizes(), &[2, 3]);
+        assert_eq!(tensor.narrow(0, 0, 1)?.expand(&[4, 3])?.sizes(), &[4, 3]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn dot_mv() -> Res<()> {
+        let lhs = Tensor::new(&[1, 2, 3, 4], &[4])?;
+        let rhs = Tensor::new(&[5, -1, 0, 2], &[4])?;
+
+        let dot = lhs.dot(&rhs)?;
+        assert_eq!(dot.sizes(), &[1]);
+        assert_eq!(dot.data(), vec![11]);
+
+        let matrix = Tensor::arange(0, 12, 1)?.view(&[3, 4])?;
+        let product = matrix.mv(&lhs)?;
+        assert_eq!(product.sizes(), &[3]);
+        assert_eq!(product.data(), vec![20, 60, 100]);
+        assert!(product.equal(&matrix.matmul(&lhs)?));
+        assert_eq!(
+            matrix
+                .transpose(0, 1)?
+                .mv(&Tensor::new(&[1, 0, 1], &[3])?)?
+                .data(),
+            vec![8, 10, 12, 14]
+        );
+
+        assert!(lhs.dot(&Tensor::new(&[1, 2, 3], &[3])?).is_err());
+        assert!(lhs.dot(&matrix).is_err());
+        assert!(matrix.mv(&Tensor::new(&[1, 2, 3], &[3])?).is_err());
+        assert!(matrix.mv(&matrix).is_err());
+        assert!(lhs.mv(&lhs).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn permute_errors() -> Res<()> {
+        let tensor = Tensor::arange(0, 24, 1)?.view(&[2, 3, 4])?;
+
+        let repeated = tensor.permute(&[0, 1, 1]).err().unwrap();
+        assert_eq!(repeated.to_string(), "Dimension 1 repeats.");
+        assert!(tensor.permute(&[0, 1]).is_err());
+        assert!(tensor.permute(&[0, 1, 2, 3]).is_err());
+        assert!(tensor.permute(&[0, 1, 3]).is_err());
+        assert_eq!(tensor.permute(&[2, 0, 1])?.sizes(), &[4, 2, 3]);
+
+        assert!(tensor.transpose(0, 3).is_err());
+        assert!(tensor.transpose(5, 1).is_err());
+        assert!(tensor.transpose(1, 1)?.equal(&tensor));
+
+        assert!(tensor.flip(&[2, 2]).is_err());
+        assert!(tensor.flip(&[3]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn outer() -> Res<()> {
+        let lhs = Tensor::new(&[1, 2, 3], &[3])?;
+        let rhs = Tensor::new(&[4, 5], &[2])?;
+
+        let product = lhs.outer(&rhs)?;
+        assert_eq!(product.sizes(), &[3, 2]);
+        assert_eq!(product.data(), vec![4, 5, 8, 10, 12, 15]);
+        assert!(product.equal(&lhs.outer_map(&rhs, |l, r| l * r)?));
+        assert_eq!(rhs.outer(&lhs)?.data(), vec![4, 8, 12, 5, 10, 15]);
+
+        assert!(lhs.outer(&product).is_err());
+        assert!(product.outer(&rhs).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn reshape_shares_contiguous() -> Res<()> {
+        let tensor = Tensor::arange(0, 12, 1)?.view(&[3, 4])?;
+
+        let reshaped = tensor.reshape(&[2, 6])?;
+        assert!(Arc::ptr_eq(&reshaped.data, &tensor.data));
+        assert_eq!(reshaped.data(), tensor.data());
+        assert!(Arc::ptr_eq(&tensor.flatten()?.data, &tensor.data));
+
+        let narrowed = tensor.narrow(0, 1, 2)?.reshape(&[4, 2])?;
+        assert!(Arc::ptr_eq(&narrowed.data, &tensor.data));
+        assert_eq!(narrowed.data(), (4..12).collect::<Vec<i32>>());
+
+        let flipped = tensor.flip(&[0, 1])?.reshape(&[12])?;
+        assert!(Arc::ptr_eq(&flipped.data, &tensor.data));
+        assert_eq!(flipped.data(), (0..12).rev().collect::<Vec<i32>>());
+
+        let transposed = tensor.transpose(0, 1)?.reshape(&[12])?;
+        assert!(!Arc::ptr_eq(&transposed.data, &tensor.data));
+        assert!(transposed.is_contiguous());
+        assert_eq!(
+            transposed.data(),
+            vec![0, 4, 8, 1, 5, 9, 2, 6, 10, 3, 7, 11]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn correlate2d() -> Res<()> {
+        use crate::conv::{correlate2d, Mode};
+
+        let image = Tensor::<i32>::arange(1, 10, 1)?.view(&[3, 3])?;
+        let box_filter = Tensor::<i32>::ones(9)?.view(&[3, 3])?;
+
+        let blurred = correlate2d(&image, &box_filter, Mode::Same)?;
+        assert_eq!(blurred.sizes(), &[3, 3]);
+        assert_eq!(blurred.data(), vec![12, 21, 16, 27, 45, 33, 24, 39, 28]);
+
+        let edges = Tensor::new(&[1, 0, -1], &[1, 3])?;
+        assert_eq!(
+            correlate2d(&image, &edges, Mode::Same)?.data(),
+            vec![-2, -2, 2, -5, -2, 5, -8, -2, 8]
+        );
+
+        let square = Tensor::<i32>::ones(4)?.view(&[2, 2])?;
+        let valid = correlate2d(&image, &square, Mode::Valid)?;
+        assert_eq!(valid.sizes(), &[2, 2]);
+        assert_eq!(valid.data(), vec![12, 16, 24, 28]);
+
+        let full = correlate2d(&image, &square, Mode::Full)?;
+        assert_eq!(full.sizes(), &[4, 4]);
+        assert_eq!(
+            full.data(),
+            image.correlate_2d(&square, &[1, 1], Mode::Full)?.data()
+        );
+        assert_eq!(
+            correlate2d(&image, &square, Mode::Same)?.data(),
+            image.correlate_2d(&square, &[1, 1], Mode::Same)?.data()
+        );
+        assert_eq!(
+            correlate2d(&image.transpose(0, 1)?, &box_filter, Mode::Same)?.data(),
+            blurred.transpose(0, 1)?.data()
+        );
+
+        assert!(correlate2d(&square, &image, Mode::Valid).is_err());
+        assert!(correlate2d(&image.view(&[9])?, &square, Mode::Same).is_err());
+
+        Ok(())
+    }
 }
@@ -1,10 +1,25 @@
-use crate::core::errors::UsizeCastError;
+use crate::core::errors::{UsizeCastError, VenumError};
 use num_traits::FromPrimitive;
 use prettytable::{format::TableFormat, Table};
 use std::any::type_name;
 
 /// Type alias for ease of use.
-pub(crate) type Res<U> = Result<U, Box<dyn std::error::Error>>;
+pub(crate) type Res<U> = Result<U, VenumError>;
+
+/// Bound on the elements and closures of the elementwise maps and `reduce`. With the `rayon`
+/// feature it means `Send + Sync`, so large contiguous tensors can be split across threads.
+/// Without it, every type implements it.
+#[cfg(feature = "rayon")]
+pub trait MaybeSendSync: Send + Sync {}
+
+#[cfg(feature = "rayon")]
+impl<T: Send + Sync> MaybeSendSync for T {}
+
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSendSync {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T> MaybeSendSync for T {}
 
 pub(crate) fn cast_usize<T>(value: usize) -> Result<T, UsizeCastError>
 where
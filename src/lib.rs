@@ -12,4 +12,15 @@ Vectorized N-dimensional numerical arrays.
 
 mod core;
 pub use core::conv;
-pub use core::Tensor;
+pub use core::errors;
+pub use core::{
+    pad_sequence, sequence_mask, BorderMode, IndexMode, MaybeSendSync, PadAlign, Reduction, Tensor,
+};
+
+/// Multiplicative identity backing `ones` and `eye`, implemented for all primitive integer and
+/// float types. Implement it (along with `Mul`) for custom element types.
+pub use num_traits::One;
+
+/// Additive identity backing `zeroes`, `eye` and `pad_zeroes`. Implement it (along with `Add`)
+/// for custom element types whose `Default` is not the additive identity.
+pub use num_traits::Zero;